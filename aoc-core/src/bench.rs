@@ -0,0 +1,184 @@
+//! Benchmarking of a [`Solution`].
+//!
+//! Parsing and both parts are measured separately. Every stage is first warmed up, which also gives
+//! an estimate of its running time; that estimate is then used to pick how many iterations to run
+//! per sample, so that all samples together roughly take [`Config::measurement`].
+
+use std::{
+  fmt::{self, Display},
+  hint::black_box,
+  str::FromStr,
+  time::{Duration, Instant},
+};
+
+use crate::{parse::ParseError, Solution};
+
+/// Stage of a solution being measured.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Stage {
+  Parse,
+  Part1,
+  Part2,
+}
+
+impl Stage {
+  pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+}
+
+impl Display for Stage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Stage::Parse => f.write_str("parse"),
+      Stage::Part1 => f.write_str("part1"),
+      Stage::Part2 => f.write_str("part2"),
+    }
+  }
+}
+
+impl FromStr for Stage {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "parse" => Ok(Stage::Parse),
+      "part1" => Ok(Stage::Part1),
+      "part2" => Ok(Stage::Part2),
+      _ => Err(format!("invalid stage {s:?}")),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+  /// Time spent running a stage before measuring it.
+  pub warm_up: Duration,
+
+  /// Target time spent measuring a stage.
+  pub measurement: Duration,
+
+  /// Number of samples per stage.
+  pub samples: usize,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      warm_up: Duration::from_millis(500),
+      measurement: Duration::from_secs(2),
+      samples: 50,
+    }
+  }
+}
+
+/// Statistics of the time per iteration of a stage, in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+  pub samples: usize,
+  pub iterations: u64,
+  pub mean: f64,
+  pub median: f64,
+  pub min: f64,
+  pub max: f64,
+  pub std_dev: f64,
+}
+
+impl Stats {
+  /// Compute statistics out of the times per iteration of every sample.
+  fn new(mut times: Vec<f64>, iterations: u64) -> Self {
+    times.sort_by(f64::total_cmp);
+
+    let samples = times.len();
+    let n = samples as f64;
+    let mean = times.iter().sum::<f64>() / n;
+    let median = if samples.is_multiple_of(2) {
+      (times[samples / 2 - 1] + times[samples / 2]) / 2.
+    } else {
+      times[samples / 2]
+    };
+    let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.).max(1.);
+
+    Self {
+      samples,
+      iterations,
+      mean,
+      median,
+      min: times[0],
+      max: times[samples - 1],
+      std_dev: variance.sqrt(),
+    }
+  }
+}
+
+/// Measure `routine`.
+pub fn measure<T>(config: &Config, mut routine: impl FnMut() -> T) -> Stats {
+  // warm up, estimating the time of a single iteration
+  let start = Instant::now();
+  let mut warm_up_iterations = 0u64;
+
+  while warm_up_iterations == 0 || start.elapsed() < config.warm_up {
+    black_box(routine());
+    warm_up_iterations += 1;
+  }
+
+  let estimate = start.elapsed().as_nanos() as f64 / warm_up_iterations as f64;
+  let samples = config.samples.max(1);
+  let per_sample = config.measurement.as_nanos() as f64 / samples as f64;
+  let iterations = ((per_sample / estimate.max(1.)) as u64).max(1);
+
+  let times = (0..samples)
+    .map(|_| {
+      let start = Instant::now();
+
+      for _ in 0..iterations {
+        black_box(routine());
+      }
+
+      start.elapsed().as_nanos() as f64 / iterations as f64
+    })
+    .collect();
+
+  Stats::new(times, iterations)
+}
+
+/// Statistics of every measured stage.
+pub type Measurements = Vec<(Stage, Stats)>;
+
+/// Measure the requested `stages` of `S` on `input`.
+pub fn bench<S>(input: &str, stages: &[Stage], config: &Config) -> Result<Measurements, ParseError>
+where
+  S: Solution,
+{
+  let parsed = S::parse(input)?;
+
+  let measurements = stages
+    .iter()
+    .map(|&stage| {
+      let stats = match stage {
+        Stage::Parse => measure(config, || S::parse(black_box(input))),
+        Stage::Part1 => measure(config, || S::part1(black_box(&parsed))),
+        Stage::Part2 => measure(config, || S::part2(black_box(&parsed))),
+      };
+      (stage, stats)
+    })
+    .collect();
+
+  Ok(measurements)
+}
+
+#[cfg(test)]
+mod test {
+  use super::Stats;
+
+  #[test]
+  fn stats() {
+    let stats = Stats::new(vec![4., 1., 3., 2.], 10);
+
+    assert_eq!(stats.samples, 4);
+    assert_eq!(stats.iterations, 10);
+    assert_eq!(stats.mean, 2.5);
+    assert_eq!(stats.median, 2.5);
+    assert_eq!(stats.min, 1.);
+    assert_eq!(stats.max, 4.);
+    assert!((stats.std_dev - (5f64 / 3.).sqrt()).abs() < 1e-9);
+  }
+}
@@ -0,0 +1,162 @@
+//! Regression checks of the answers of a [`Solution`].
+//!
+//! Every day declares the examples of its puzzle statement as [`Solution::EXAMPLES`]; [`examples`]
+//! checks all of them. Real inputs are private and cannot be committed, so [`input`] only checks
+//! them when `inputs/dayNN.txt` and its expected answers, `inputs/dayNN.answers` (one line per part),
+//! are present on disk.
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use crate::{input::default_path, Part, Solution};
+
+/// An example input and its expected answers.
+///
+/// Parts without expected answer are not checked, as some examples only apply to one part.
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+  input: &'static str,
+  part1: Option<&'static str>,
+  part2: Option<&'static str>,
+}
+
+impl Example {
+  pub const fn new(input: &'static str) -> Self {
+    Self {
+      input,
+      part1: None,
+      part2: None,
+    }
+  }
+
+  pub const fn part1(self, answer: &'static str) -> Self {
+    Self {
+      part1: Some(answer),
+      ..self
+    }
+  }
+
+  pub const fn part2(self, answer: &'static str) -> Self {
+    Self {
+      part2: Some(answer),
+      ..self
+    }
+  }
+
+  pub fn input(&self) -> &'static str {
+    self.input
+  }
+
+  /// Expected answer of `part`, if any.
+  pub fn answer(&self, part: Part) -> Option<&'static str> {
+    match part {
+      Part::One => self.part1,
+      Part::Two => self.part2,
+    }
+  }
+}
+
+/// Check `input` against the `expected` answers; return a description of every mismatch.
+fn mismatches<S>(input: &str, expected: [Option<&str>; 2]) -> Vec<String>
+where
+  S: Solution,
+{
+  let input = match S::parse(input) {
+    Ok(input) => input,
+    Err(err) => return vec![err.to_string()],
+  };
+
+  Part::ALL
+    .into_iter()
+    .zip(expected)
+    .filter_map(|(part, expected)| {
+      let expected = expected?;
+      let answer = match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+      };
+
+      (answer != expected).then(|| format!("part {part}: expected {expected}, got {answer}"))
+    })
+    .collect()
+}
+
+/// Check all the examples of `S`.
+///
+/// # Panics
+///
+/// Panics if an example does not parse or yields an unexpected answer.
+pub fn examples<S>()
+where
+  S: Solution,
+{
+  assert!(!S::EXAMPLES.is_empty(), "day {:02}: no example", S::DAY);
+
+  let failures: Vec<_> = S::EXAMPLES
+    .iter()
+    .enumerate()
+    .flat_map(|(i, example)| {
+      mismatches::<S>(example.input, [example.part1, example.part2])
+        .into_iter()
+        .map(move |mismatch| format!("example {}: {mismatch}", i + 1))
+    })
+    .collect();
+
+  assert!(
+    failures.is_empty(),
+    "day {:02}:\n{}",
+    S::DAY,
+    failures.join("\n")
+  );
+}
+
+/// Location of the private inputs, at the root of the workspace.
+fn input_path(day: u8) -> PathBuf {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .parent()
+    .expect("workspace root");
+  root.join(default_path(day))
+}
+
+/// Check the private input of `S`, if present.
+///
+/// # Panics
+///
+/// Panics if the input does not parse or yields an unexpected answer.
+pub fn input<S>()
+where
+  S: Solution,
+{
+  let path = input_path(S::DAY);
+  let (Ok(input), Ok(answers)) = (
+    fs::read_to_string(&path),
+    fs::read_to_string(path.with_extension("answers")),
+  ) else {
+    eprintln!("day {:02}: no input or answers; skipping", S::DAY);
+    return;
+  };
+
+  let mut answers = answers.lines().map(str::trim).filter(|a| !a.is_empty());
+  let failures = mismatches::<S>(&input, [answers.next(), answers.next()]);
+
+  assert!(
+    failures.is_empty(),
+    "day {:02}, {}:\n{}",
+    S::DAY,
+    path.display(),
+    failures.join("\n")
+  );
+}
+
+/// xorshift generator for the randomised tests of the days, so that failures are reproducible.
+#[cfg(feature = "test-utils")]
+pub fn rng(mut state: u64) -> impl FnMut() -> u64 {
+  move || {
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    state
+  }
+}
@@ -0,0 +1,336 @@
+//! 2D grids.
+//!
+//! A [`Grid`] stores its cells row by row in a single vector, and is addressed with [`Pos`]itions.
+//! Rows go down and columns go right, so that a grid reads like the text it was parsed from.
+
+use std::{
+  fmt::{self, Display},
+  ops::{Index, IndexMut},
+};
+
+use crate::parse::{Lines, ParseError};
+
+/// Position of a cell in a [`Grid`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Pos {
+  pub row: usize,
+  pub col: usize,
+}
+
+impl Pos {
+  pub const fn new(row: usize, col: usize) -> Self {
+    Self { row, col }
+  }
+
+  /// Position one step away in the direction `dir`, if it does not underflow.
+  pub fn step(self, dir: Dir) -> Option<Self> {
+    self.offset(dir.delta())
+  }
+
+  /// Position offset by `(rows, cols)`, if it does not underflow.
+  pub fn offset(self, (rows, cols): (isize, isize)) -> Option<Self> {
+    Some(Self {
+      row: self.row.checked_add_signed(rows)?,
+      col: self.col.checked_add_signed(cols)?,
+    })
+  }
+}
+
+impl Display for Pos {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "({}, {})", self.row, self.col)
+  }
+}
+
+/// Cardinal direction.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir {
+  Up,
+  Right,
+  Down,
+  Left,
+}
+
+impl Dir {
+  /// All directions, clockwise.
+  pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+  /// `(rows, cols)` offset of a step in that direction.
+  pub fn delta(self) -> (isize, isize) {
+    match self {
+      Dir::Up => (-1, 0),
+      Dir::Right => (0, 1),
+      Dir::Down => (1, 0),
+      Dir::Left => (0, -1),
+    }
+  }
+
+  pub fn opposite(self) -> Self {
+    match self {
+      Dir::Up => Dir::Down,
+      Dir::Right => Dir::Left,
+      Dir::Down => Dir::Up,
+      Dir::Left => Dir::Right,
+    }
+  }
+}
+
+/// Offsets of the 8 neighbours of a cell, row by row.
+const NEIGHBOURS8: [(isize, isize); 8] = [
+  (-1, -1),
+  (-1, 0),
+  (-1, 1),
+  (0, -1),
+  (0, 1),
+  (1, -1),
+  (1, 0),
+  (1, 1),
+];
+
+/// Rectangular grid of cells.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  /// Build a grid out of its `cells`, row by row.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there are not exactly `width * height` cells.
+  pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+    assert_eq!(
+      cells.len(),
+      width * height,
+      "{width}x{height} grid with {} cells",
+      cells.len()
+    );
+
+    Self {
+      width,
+      height,
+      cells,
+    }
+  }
+
+  pub fn filled(width: usize, height: usize, value: T) -> Self
+  where
+    T: Clone,
+  {
+    Self::new(width, height, vec![value; width * height])
+  }
+
+  /// Parse a grid, one row per line and one cell per character.
+  ///
+  /// `cell` turns a character into a cell, or rejects it with `None`, in which case `expected` is
+  /// reported. All rows must have the same width.
+  pub fn parse(
+    day: u8,
+    input: &str,
+    expected: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+  ) -> Result<Self, ParseError> {
+    let mut width = None;
+    let mut height = 0;
+    let mut cells = Vec::new();
+
+    for line in Lines::new(day, input) {
+      let text = line.text();
+      let mut row_width = 0;
+
+      for (i, c) in text.char_indices() {
+        let value = cell(c).ok_or_else(|| line.error(&text[i..i + c.len_utf8()], expected))?;
+        cells.push(value);
+        row_width += 1;
+      }
+
+      match width {
+        None => width = Some(row_width),
+        Some(width) if width != row_width => {
+          let at = text
+            .char_indices()
+            .nth(width)
+            .map_or(&text[text.len()..], |(i, _)| &text[i..]);
+          return Err(line.error(at, &format!("a row of {width} cells")));
+        }
+        _ => (),
+      }
+
+      height += 1;
+    }
+
+    Ok(Self::new(width.unwrap_or(0), height, cells))
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn contains(&self, pos: Pos) -> bool {
+    pos.row < self.height && pos.col < self.width
+  }
+
+  fn offset(&self, pos: Pos) -> Option<usize> {
+    self.contains(pos).then_some(pos.row * self.width + pos.col)
+  }
+
+  pub fn get(&self, pos: Pos) -> Option<&T> {
+    self.offset(pos).map(|i| &self.cells[i])
+  }
+
+  pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+    self.offset(pos).map(|i| &mut self.cells[i])
+  }
+
+  /// All positions, row by row.
+  pub fn positions(&self) -> impl Iterator<Item = Pos> {
+    let width = self.width;
+    (0..self.height).flat_map(move |row| (0..width).map(move |col| Pos::new(row, col)))
+  }
+
+  /// All cells along with their positions, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+    self.positions().zip(&self.cells)
+  }
+
+  /// Position of the first cell, row by row, satisfying `pred`.
+  pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+    self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+  }
+
+  pub fn row(&self, row: usize) -> Option<&[T]> {
+    (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // chunks() panics on empty chunks
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+    let cells = if col < self.width {
+      &self.cells[col..]
+    } else {
+      &[]
+    };
+    cells.iter().step_by(self.width.max(1))
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(|col| self.column(col))
+  }
+
+  /// Neighbours of `pos` in the 4 cardinal directions, within the grid.
+  pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+    Dir::ALL
+      .into_iter()
+      .filter_map(move |dir| pos.step(dir))
+      .filter(|pos| self.contains(*pos))
+  }
+
+  /// Neighbours of `pos`, including diagonals, within the grid.
+  pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+    NEIGHBOURS8
+      .into_iter()
+      .filter_map(move |delta| pos.offset(delta))
+      .filter(|pos| self.contains(*pos))
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, pos: Pos) -> &Self::Output {
+    self
+      .get(pos)
+      .unwrap_or_else(|| panic!("{pos} out of {}x{} grid", self.width, self.height))
+  }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+  fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+    let (width, height) = (self.width, self.height);
+    self
+      .get_mut(pos)
+      .unwrap_or_else(|| panic!("{pos} out of {width}x{height} grid"))
+  }
+}
+
+impl<T> Display for Grid<T>
+where
+  T: Display,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for row in self.rows() {
+      for cell in row {
+        write!(f, "{cell}")?;
+      }
+
+      writeln!(f)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{Dir, Grid, Pos};
+
+  #[test]
+  fn parse() {
+    let grid = Grid::parse(0, "ab\ncd\nef", "a letter", Some).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (2, 3));
+    assert_eq!(grid[Pos::new(2, 1)], 'f');
+    assert_eq!(grid.get(Pos::new(3, 0)), None);
+    assert_eq!(grid.row(1), Some(&['c', 'd'][..]));
+    assert_eq!(grid.column(1).collect::<String>(), "bdf");
+    assert_eq!(grid.columns().count(), 2);
+    assert_eq!(grid.find(|c| *c == 'd'), Some(Pos::new(1, 1)));
+    assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+
+    let err = Grid::parse(0, "ab\nc\n", "a letter", Some).unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 2));
+    assert_eq!(err.expected(), "a row of 2 cells");
+
+    let err = Grid::parse(0, "ab\nc!\n", "a letter", |c| {
+      c.is_alphabetic().then_some(c)
+    })
+    .unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 2));
+  }
+
+  #[test]
+  fn neighbours() {
+    let grid = Grid::filled(3, 3, ());
+
+    assert_eq!(
+      grid.neighbours4(Pos::new(0, 0)).collect::<Vec<_>>(),
+      [Pos::new(0, 1), Pos::new(1, 0)]
+    );
+    assert_eq!(grid.neighbours4(Pos::new(1, 1)).count(), 4);
+    assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 8);
+    assert_eq!(
+      grid.neighbours8(Pos::new(2, 2)).collect::<Vec<_>>(),
+      [Pos::new(1, 1), Pos::new(1, 2), Pos::new(2, 1)]
+    );
+    assert_eq!(Pos::new(0, 0).step(Dir::Up), None);
+    assert_eq!(Dir::Left.opposite(), Dir::Right);
+  }
+}
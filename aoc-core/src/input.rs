@@ -0,0 +1,101 @@
+//! Puzzle input loading.
+//!
+//! Inputs are read at runtime so that the same binary can be used against any number of inputs.
+//! An input is looked up, in order, from:
+//!
+//! 1. an explicit path;
+//! 2. stdin, if the path is `-`;
+//! 3. the conventional `inputs/dayNN.txt` location, relative to the current directory.
+
+use std::{
+  fmt, fs,
+  io::{self, BufRead, BufReader, Read as _},
+  path::{Path, PathBuf},
+};
+
+/// Where an input is read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+  Path(PathBuf),
+  Stdin,
+}
+
+impl Source {
+  /// Source for the given `day` and optional command-line argument.
+  pub fn new(day: u8, arg: Option<&str>) -> Self {
+    match arg {
+      Some("-") => Source::Stdin,
+      Some(path) => Source::Path(path.into()),
+      None => Source::Path(default_path(day)),
+    }
+  }
+
+  pub fn read(&self) -> Result<String, InputError> {
+    let res = match self {
+      Source::Path(path) => fs::read_to_string(path),
+      Source::Stdin => {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+      }
+    };
+
+    res.map_err(|err| self.error(err))
+  }
+
+  /// Open the input to read it line by line, rather than all at once.
+  pub fn open(&self) -> Result<Box<dyn BufRead>, InputError> {
+    match self {
+      Source::Path(path) => match fs::File::open(path) {
+        Ok(file) => Ok(Box::new(BufReader::new(file))),
+        Err(err) => Err(self.error(err)),
+      },
+      Source::Stdin => Ok(Box::new(io::stdin().lock())),
+    }
+  }
+
+  fn error(&self, err: io::Error) -> InputError {
+    InputError {
+      origin: self.clone(),
+      err,
+    }
+  }
+}
+
+impl fmt::Display for Source {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Source::Path(path) => write!(f, "{}", path.display()),
+      Source::Stdin => f.write_str("stdin"),
+    }
+  }
+}
+
+/// Conventional location of the input of `day`.
+pub fn default_path(day: u8) -> PathBuf {
+  Path::new("inputs").join(format!("day{day:02}.txt"))
+}
+
+/// Error raised when an input cannot be read.
+#[derive(Debug)]
+pub struct InputError {
+  origin: Source,
+  err: io::Error,
+}
+
+impl fmt::Display for InputError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "cannot read input from {}: {}", self.origin, self.err)?;
+
+    if self.err.kind() == io::ErrorKind::NotFound {
+      write!(f, " (pass a path to the input, or - to read it from stdin)")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl std::error::Error for InputError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    Some(&self.err)
+  }
+}
@@ -0,0 +1,182 @@
+//! Shared pieces for all the days.
+
+pub mod bench;
+pub mod check;
+pub mod grid;
+pub mod input;
+pub mod parse;
+
+use std::{
+  fmt::{self, Display},
+  process,
+  str::FromStr,
+};
+
+use crate::{
+  bench::{Config, Measurements, Stage},
+  check::Example,
+  input::Source,
+  parse::ParseError,
+};
+
+/// A puzzle solution.
+///
+/// The input is parsed once and then handed to both parts.
+pub trait Solution {
+  /// Day of the puzzle, starting at 1.
+  const DAY: u8;
+
+  /// Parsed representation of the puzzle input.
+  type Input;
+
+  /// Answer of the first part.
+  type Output1: Display;
+
+  /// Answer of the second part.
+  type Output2: Display;
+
+  /// Examples of the puzzle statement, along with their expected answers.
+  const EXAMPLES: &'static [Example] = &[];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+  fn part1(input: &Self::Input) -> Self::Output1;
+
+  fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+/// Puzzle part.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Part::One => f.write_str("1"),
+      Part::Two => f.write_str("2"),
+    }
+  }
+}
+
+impl FromStr for Part {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "1" => Ok(Part::One),
+      "2" => Ok(Part::Two),
+      _ => Err(format!("invalid part {s:?}; expected 1 or 2")),
+    }
+  }
+}
+
+/// Answers of a day, tagged with their part.
+pub type Answers = Vec<(Part, String)>;
+
+/// Type-erased [`Solution`], so that days can be picked at runtime.
+#[derive(Clone, Copy)]
+pub struct Solver {
+  day: u8,
+  examples: &'static [Example],
+  solve: fn(&str, &[Part]) -> Result<Answers, ParseError>,
+  bench: fn(&str, &[Stage], &Config) -> Result<Measurements, ParseError>,
+}
+
+impl Solver {
+  pub const fn new<S>() -> Self
+  where
+    S: Solution,
+  {
+    Self {
+      day: S::DAY,
+      examples: S::EXAMPLES,
+      solve: solve::<S>,
+      bench: bench::bench::<S>,
+    }
+  }
+
+  pub fn day(&self) -> u8 {
+    self.day
+  }
+
+  pub fn examples(&self) -> &'static [Example] {
+    self.examples
+  }
+
+  /// Parse `input` once and compute the answers of the requested `parts`, in order.
+  pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Answers, ParseError> {
+    (self.solve)(input, parts)
+  }
+
+  /// Parse `input` and measure the requested `stages`, in order.
+  pub fn bench(
+    &self,
+    input: &str,
+    stages: &[Stage],
+    config: &Config,
+  ) -> Result<Measurements, ParseError> {
+    (self.bench)(input, stages, config)
+  }
+}
+
+fn solve<S>(input: &str, parts: &[Part]) -> Result<Answers, ParseError>
+where
+  S: Solution,
+{
+  let input = S::parse(input)?;
+  let answers = parts
+    .iter()
+    .map(|&part| {
+      let answer = match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+      };
+      (part, answer)
+    })
+    .collect();
+
+  Ok(answers)
+}
+
+/// Parse `input` and print the answers of both parts.
+pub fn run<S>(input: &str) -> Result<(), ParseError>
+where
+  S: Solution,
+{
+  for (part, answer) in Solver::new::<S>().solve(input, &Part::ALL)? {
+    println!("part {part}: {answer}");
+  }
+
+  Ok(())
+}
+
+/// Entry point of the binary of a day.
+///
+/// The input is read from the path given as first argument, from stdin if that argument is `-`, or
+/// from `inputs/dayNN.txt` if no argument is given.
+pub fn main<S>()
+where
+  S: Solution,
+{
+  let arg = std::env::args().nth(1);
+
+  let input = match Source::new(S::DAY, arg.as_deref()).read() {
+    Ok(input) => input,
+    Err(err) => {
+      eprintln!("day {:02}: {err}", S::DAY);
+      process::exit(1);
+    }
+  };
+
+  if let Err(err) = run::<S>(&input) {
+    eprintln!("{err}");
+    process::exit(1);
+  }
+}
@@ -0,0 +1,258 @@
+//! Input parsing helpers.
+//!
+//! Parsers walk the input [`Line`] by [`Line`] and report malformed input as a [`ParseError`]
+//! pointing at the offending line and column.
+
+use std::{
+  collections::{hash_map::Entry, HashMap},
+  error::Error,
+  fmt,
+  hash::Hash,
+  str::FromStr,
+};
+
+/// Error raised on malformed input.
+///
+/// Lines and columns start at 1. Columns are counted in bytes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+  day: u8,
+  line: usize,
+  column: usize,
+  expected: String,
+  found: String,
+}
+
+impl ParseError {
+  pub fn day(&self) -> u8 {
+    self.day
+  }
+
+  pub fn line(&self) -> usize {
+    self.line
+  }
+
+  pub fn column(&self) -> usize {
+    self.column
+  }
+
+  /// What the parser was expecting.
+  pub fn expected(&self) -> &str {
+    &self.expected
+  }
+
+  /// What the parser found instead.
+  pub fn found(&self) -> &str {
+    &self.found
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "day {:02}, line {}, column {}: expected {}, found {}",
+      self.day, self.line, self.column, self.expected, self.found
+    )
+  }
+}
+
+impl Error for ParseError {}
+
+/// Lines of an input, numbered.
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+  day: u8,
+  number: usize,
+  lines: std::str::Lines<'a>,
+}
+
+impl<'a> Lines<'a> {
+  pub fn new(day: u8, input: &'a str) -> Self {
+    Self {
+      day,
+      number: 0,
+      lines: input.lines(),
+    }
+  }
+
+  /// Next line, failing with `expected` if the input is exhausted.
+  pub fn expect(&mut self, expected: &str) -> Result<Line<'a>, ParseError> {
+    self.next().ok_or_else(|| self.end_error(expected))
+  }
+
+  /// Error located right after the last line.
+  pub fn end_error(&self, expected: &str) -> ParseError {
+    ParseError {
+      day: self.day,
+      line: self.number + 1,
+      column: 1,
+      expected: expected.to_owned(),
+      found: "end of input".to_owned(),
+    }
+  }
+}
+
+impl<'a> Iterator for Lines<'a> {
+  type Item = Line<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let text = self.lines.next()?;
+    self.number += 1;
+
+    Some(Line {
+      day: self.day,
+      number: self.number,
+      text,
+    })
+  }
+}
+
+/// A single line of input, knowing where it is located.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+  day: u8,
+  number: usize,
+  text: &'a str,
+}
+
+impl<'a> Line<'a> {
+  /// Line number `number` of the input of `day`, for parsers reading their input line by line
+  /// rather than through [`Lines`].
+  pub fn new(day: u8, number: usize, text: &'a str) -> Self {
+    Self { day, number, text }
+  }
+
+  pub fn number(&self) -> usize {
+    self.number
+  }
+
+  pub fn text(&self) -> &'a str {
+    self.text
+  }
+
+  /// Error located at `at`, which must be a slice of [`Line::text`].
+  ///
+  /// An empty `at` is reported as the end of the line.
+  pub fn error(&self, at: &str, expected: &str) -> ParseError {
+    let offset = (at.as_ptr() as usize)
+      .checked_sub(self.text.as_ptr() as usize)
+      .filter(|offset| *offset <= self.text.len())
+      .unwrap_or(0);
+    let found = if at.is_empty() {
+      "end of line".to_owned()
+    } else {
+      format!("{at:?}")
+    };
+
+    ParseError {
+      day: self.day,
+      line: self.number,
+      column: offset + 1,
+      expected: expected.to_owned(),
+      found,
+    }
+  }
+
+  /// Error located at the end of `s`, which must be a slice of [`Line::text`].
+  pub fn error_after(&self, s: &str, expected: &str) -> ParseError {
+    self.error(&s[s.len()..], expected)
+  }
+
+  /// Parse `token`, which must be a slice of [`Line::text`].
+  pub fn parse<T>(&self, token: &str, expected: &str) -> Result<T, ParseError>
+  where
+    T: FromStr,
+  {
+    token.parse().map_err(|_| self.error(token, expected))
+  }
+
+  /// Split `s`, which must be a slice of [`Line::text`], around the first `sep`.
+  pub fn split_once(&self, s: &'a str, sep: char) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(sep)
+      .ok_or_else(|| self.error_after(s, &format!("{sep:?}")))
+  }
+
+  /// Strip `prefix` from `s`, which must be a slice of [`Line::text`].
+  pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix).ok_or_else(|| {
+      let end = s
+        .char_indices()
+        .zip(prefix.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(s.len(), |((i, _), _)| i);
+      self.error(&s[end..], &format!("{prefix:?}"))
+    })
+  }
+
+  /// Record in `declared` that `key`, spelt `token`, is declared on this line, failing if another
+  /// line already declared it.
+  ///
+  /// `what` describes the key, e.g. `"a game ID"`; `token` must be a slice of [`Line::text`].
+  pub fn unique<K>(
+    &self,
+    declared: &mut HashMap<K, usize>,
+    key: K,
+    token: &str,
+    what: &str,
+  ) -> Result<(), ParseError>
+  where
+    K: Eq + Hash,
+  {
+    match declared.entry(key) {
+      Entry::Occupied(first) => Err(self.error(
+        token,
+        &format!("{what} other than the one on line {}", first.get()),
+      )),
+      Entry::Vacant(entry) => {
+        entry.insert(self.number);
+        Ok(())
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::HashMap;
+
+  use super::Lines;
+
+  #[test]
+  fn error_location() {
+    let mut lines = Lines::new(2, "Game 1: 3 blue\nGame 2: x red");
+    lines.next();
+    let line = lines.next().unwrap();
+    let (_, rounds) = line.split_once(line.text(), ':').unwrap();
+    let n = rounds.split_whitespace().next().unwrap();
+    let err = line.parse::<u32>(n, "a number of cubes").unwrap_err();
+
+    assert_eq!((err.day(), err.line(), err.column()), (2, 2, 9));
+    assert_eq!(
+      err.to_string(),
+      "day 02, line 2, column 9: expected a number of cubes, found \"x\""
+    );
+    assert_eq!(
+      lines.expect("another game").unwrap_err().to_string(),
+      "day 02, line 3, column 1: expected another game, found end of input"
+    );
+  }
+
+  #[test]
+  fn unique_keys() {
+    let mut declared = HashMap::new();
+    let lines: Vec<_> = Lines::new(4, "Card 1\nCard 2\nCard 1").collect();
+
+    for line in &lines[..2] {
+      let id = line.strip_prefix(line.text(), "Card ").unwrap();
+      line.unique(&mut declared, id, id, "a card ID").unwrap();
+    }
+
+    let line = &lines[2];
+    let id = line.strip_prefix(line.text(), "Card ").unwrap();
+    let err = line.unique(&mut declared, id, id, "a card ID").unwrap_err();
+    assert_eq!((err.line(), err.column()), (3, 6));
+    assert_eq!(err.expected(), "a card ID other than the one on line 1");
+    assert_eq!(declared["1"], 1);
+  }
+}
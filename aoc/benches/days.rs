@@ -0,0 +1,283 @@
+//! Benchmarks of all the days, measuring parsing and both parts separately.
+//!
+//! Run with `cargo bench -p aoc -- [DAY…] [--quick] [--output <PATH>] [--baseline <PATH>]`.
+//!
+//! A day is measured on its private input, `inputs/dayNN.txt`, when present, and on its examples
+//! otherwise. The results are written as CSV to `target/aoc-bench.csv` by default. Pass a previous
+//! report as `--baseline` to compare against it. Relative paths are resolved from the root of the
+//! workspace.
+
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+  process,
+  time::Duration,
+};
+
+use aoc::DAYS;
+use aoc_core::{
+  bench::{Config, Stage, Stats},
+  input::default_path,
+  Part, Solver,
+};
+
+const HEADER: &str =
+  "day,stage,input,samples,iterations,mean_ns,median_ns,min_ns,max_ns,std_dev_ns";
+
+/// Relative change of the mean above which a stage is reported as regressed or improved.
+const THRESHOLD: f64 = 0.05;
+
+#[derive(Debug)]
+struct Args {
+  days: Vec<u8>,
+  quick: bool,
+  output: PathBuf,
+  baseline: Option<PathBuf>,
+}
+
+impl Args {
+  fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+    let mut parsed = Args {
+      days: Vec::new(),
+      quick: false,
+      output: workspace_path("target/aoc-bench.csv"),
+      baseline: None,
+    };
+
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        // passed by cargo bench
+        "--bench" => (),
+        "--quick" => parsed.quick = true,
+        "--output" => parsed.output = workspace_path(Self::value(&mut args, &arg)?),
+        "--baseline" => parsed.baseline = Some(workspace_path(Self::value(&mut args, &arg)?)),
+        day => parsed
+          .days
+          .push(day.parse().map_err(|_| format!("invalid day {day:?}"))?),
+      }
+    }
+
+    Ok(parsed)
+  }
+
+  fn value(mut args: impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args
+      .next()
+      .ok_or_else(|| format!("missing value for {flag}"))
+  }
+
+  fn config(&self) -> Config {
+    if self.quick {
+      Config {
+        warm_up: Duration::from_millis(100),
+        measurement: Duration::from_millis(300),
+        samples: 20,
+      }
+    } else {
+      Config::default()
+    }
+  }
+}
+
+/// Measurement of a single stage, as reported.
+#[derive(Debug)]
+struct Row {
+  day: u8,
+  stage: Stage,
+  input: String,
+  stats: Stats,
+}
+
+impl Row {
+  fn to_csv(&self) -> String {
+    let Stats {
+      samples,
+      iterations,
+      mean,
+      median,
+      min,
+      max,
+      std_dev,
+    } = &self.stats;
+
+    format!(
+      "{},{},{},{samples},{iterations},{mean:.1},{median:.1},{min:.1},{max:.1},{std_dev:.1}",
+      self.day, self.stage, self.input
+    )
+  }
+}
+
+/// Resolve `path` relative to the root of the workspace, as cargo runs benchmarks from the root of
+/// their package.
+fn workspace_path(path: impl AsRef<Path>) -> PathBuf {
+  let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .parent()
+    .expect("workspace root");
+  root.join(path)
+}
+
+/// Inputs to measure `solver` on, along with the stages to measure on each of them.
+fn inputs(solver: &Solver) -> Vec<(String, String, Vec<Stage>)> {
+  let path = default_path(solver.day());
+
+  if let Ok(input) = fs::read_to_string(workspace_path(&path)) {
+    return vec![(path.display().to_string(), input, Stage::ALL.to_vec())];
+  }
+
+  // examples do not necessarily apply to both parts; only measure a part on the first example
+  // expecting an answer for it
+  let mut left = Stage::ALL.to_vec();
+
+  solver
+    .examples()
+    .iter()
+    .enumerate()
+    .filter_map(|(i, example)| {
+      let stages: Vec<_> = left
+        .iter()
+        .copied()
+        .filter(|stage| match stage {
+          Stage::Parse => true,
+          Stage::Part1 => example.answer(Part::One).is_some(),
+          Stage::Part2 => example.answer(Part::Two).is_some(),
+        })
+        .collect();
+      left.retain(|stage| !stages.contains(stage));
+
+      (!stages.is_empty()).then(|| {
+        (
+          format!("example {}", i + 1),
+          example.input().to_owned(),
+          stages,
+        )
+      })
+    })
+    .collect()
+}
+
+/// Read the mean time of every stage of a previous report.
+fn read_baseline(path: &Path) -> Result<HashMap<(u8, Stage), f64>, String> {
+  let report = fs::read_to_string(path)
+    .map_err(|err| format!("cannot read baseline {}: {err}", path.display()))?;
+
+  report
+    .lines()
+    .skip(1)
+    .filter(|line| !line.is_empty())
+    .map(|line| {
+      let fields: Vec<_> = line.split(',').collect();
+      let invalid = || format!("invalid baseline line {line:?}");
+      let day = fields
+        .first()
+        .and_then(|f| f.parse().ok())
+        .ok_or_else(invalid)?;
+      let stage = fields
+        .get(1)
+        .and_then(|f| f.parse().ok())
+        .ok_or_else(invalid)?;
+      let mean = fields
+        .get(5)
+        .and_then(|f| f.parse().ok())
+        .ok_or_else(invalid)?;
+      Ok(((day, stage), mean))
+    })
+    .collect()
+}
+
+fn format_ns(ns: f64) -> String {
+  if ns < 1e3 {
+    format!("{ns:.1} ns")
+  } else if ns < 1e6 {
+    format!("{:.2} µs", ns / 1e3)
+  } else if ns < 1e9 {
+    format!("{:.2} ms", ns / 1e6)
+  } else {
+    format!("{:.2} s", ns / 1e9)
+  }
+}
+
+fn main() {
+  let args = match Args::parse(std::env::args().skip(1)) {
+    Ok(args) => args,
+    Err(err) => {
+      eprintln!("{err}");
+      process::exit(2);
+    }
+  };
+  let baseline = match args.baseline.as_deref().map(read_baseline).transpose() {
+    Ok(baseline) => baseline.unwrap_or_default(),
+    Err(err) => {
+      eprintln!("{err}");
+      process::exit(2);
+    }
+  };
+  let config = args.config();
+  let mut rows = Vec::new();
+
+  for solver in DAYS {
+    let day = solver.day();
+
+    if !args.days.is_empty() && !args.days.contains(&day) {
+      continue;
+    }
+
+    for (name, input, stages) in inputs(solver) {
+      let measurements = match solver.bench(&input, &stages, &config) {
+        Ok(measurements) => measurements,
+        Err(err) => {
+          eprintln!("{err}");
+          continue;
+        }
+      };
+
+      for (stage, stats) in measurements {
+        let change = baseline
+          .get(&(day, stage))
+          .map(|base| {
+            let change = (stats.mean - base) / base;
+            let verdict = if change > THRESHOLD {
+              " (regressed)"
+            } else if change < -THRESHOLD {
+              " (improved)"
+            } else {
+              ""
+            };
+            format!("  {:+.1}%{verdict}", change * 100.)
+          })
+          .unwrap_or_default();
+
+        println!(
+          "day {day:02} {stage:<5} [{name}]  mean {}  median {}  ± {}{change}",
+          format_ns(stats.mean),
+          format_ns(stats.median),
+          format_ns(stats.std_dev),
+        );
+
+        rows.push(Row {
+          day,
+          stage,
+          input: name.clone(),
+          stats,
+        });
+      }
+    }
+  }
+
+  let report: String = [HEADER.to_owned()]
+    .into_iter()
+    .chain(rows.iter().map(Row::to_csv))
+    .map(|line| line + "\n")
+    .collect();
+
+  if let Some(dir) = args.output.parent() {
+    let _ = fs::create_dir_all(dir);
+  }
+
+  if let Err(err) = fs::write(&args.output, report) {
+    eprintln!("cannot write report {}: {err}", args.output.display());
+    process::exit(1);
+  }
+
+  println!("report written to {}", args.output.display());
+}
@@ -0,0 +1,17 @@
+//! Registry of all the days.
+
+use aoc_core::Solver;
+
+/// All the registered days.
+pub const DAYS: &[Solver] = &[
+  Solver::new::<day01::Day01>(),
+  Solver::new::<day02::Day02>(),
+  Solver::new::<day03::Day03>(),
+  Solver::new::<day04::Day04>(),
+  Solver::new::<day05::Day05>(),
+  Solver::new::<day06::Day06>(),
+  Solver::new::<day07::Day07>(),
+  Solver::new::<day08::Day08>(),
+  Solver::new::<day09::Day09>(),
+  Solver::new::<day10::Day10>(),
+];
@@ -0,0 +1,121 @@
+use std::process;
+
+use aoc::DAYS;
+use aoc_core::{input::Source, Part, Solver};
+
+const USAGE: &str = "\
+usage: aoc run <DAY> [--part <1|2>] [--input <PATH|->]
+       aoc all
+
+Inputs default to inputs/dayNN.txt.";
+
+#[derive(Debug)]
+enum Command {
+  Run {
+    day: u8,
+    part: Option<Part>,
+    input: Option<String>,
+  },
+  All,
+}
+
+impl Command {
+  fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+    match args.next().as_deref() {
+      Some("run") => {
+        let day = args.next().ok_or("missing day")?;
+        let day = day.parse().map_err(|_| format!("invalid day {day:?}"))?;
+        let mut part = None;
+        let mut input = None;
+
+        while let Some(arg) = args.next() {
+          match arg.as_str() {
+            "--part" | "-p" => part = Some(Self::value(&mut args, &arg)?.parse()?),
+            "--input" | "-i" => input = Some(Self::value(&mut args, &arg)?),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+          }
+        }
+
+        Ok(Command::Run { day, part, input })
+      }
+
+      Some("all") => match args.next() {
+        Some(arg) => Err(format!("unexpected argument {arg:?}")),
+        None => Ok(Command::All),
+      },
+
+      Some(cmd) => Err(format!("unknown command {cmd:?}")),
+      None => Err("missing command".to_owned()),
+    }
+  }
+
+  fn value(mut args: impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args
+      .next()
+      .ok_or_else(|| format!("missing value for {flag}"))
+  }
+}
+
+/// Run a single day and print its answers; return whether it succeeded.
+fn run(solver: &Solver, parts: &[Part], source: &Source) -> bool {
+  let day = solver.day();
+
+  let input = match source.read() {
+    Ok(input) => input,
+    Err(err) => {
+      eprintln!("day {day:02}: {err}");
+      return false;
+    }
+  };
+
+  match solver.solve(&input, parts) {
+    Ok(answers) => {
+      for (part, answer) in answers {
+        println!("day {day:02} part {part}: {answer}");
+      }
+      true
+    }
+
+    Err(err) => {
+      eprintln!("{err}");
+      false
+    }
+  }
+}
+
+fn main() {
+  let cmd = match Command::parse(std::env::args().skip(1)) {
+    Ok(cmd) => cmd,
+    Err(err) => {
+      eprintln!("{err}\n\n{USAGE}");
+      process::exit(2);
+    }
+  };
+
+  let ok = match cmd {
+    Command::Run { day, part, input } => {
+      let Some(solver) = DAYS.iter().find(|solver| solver.day() == day) else {
+        eprintln!("day {day} is not registered");
+        process::exit(2);
+      };
+      let parts = part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+
+      run(solver, &parts, &Source::new(day, input.as_deref()))
+    }
+
+    Command::All => {
+      // keep going when a day fails, so that all the errors are reported
+      let mut ok = true;
+
+      for solver in DAYS {
+        ok &= run(solver, &Part::ALL, &Source::new(solver.day(), None));
+      }
+
+      ok
+    }
+  };
+
+  if !ok {
+    process::exit(1);
+  }
+}
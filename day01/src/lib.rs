@@ -0,0 +1,374 @@
+use std::{cmp::Reverse, collections::VecDeque, error::Error, fmt};
+
+use aoc_core::{check::Example, parse::ParseError, Solution};
+
+/// Both parts skip lines without any digit; [`calibrations`] reports them as [`NoDigit`].
+pub struct Day01;
+
+impl Solution for Day01 {
+  type Input = Vec<String>;
+  type Output1 = u32;
+  type Output2 = u32;
+
+  const DAY: u8 = 1;
+  const EXAMPLES: &'static [Example] = &[
+    Example::new(include_str!("example1.txt")).part1("142"),
+    Example::new(include_str!("example2.txt")).part2("281"),
+  ];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    Ok(input.lines().map(str::to_owned).collect())
+  }
+
+  fn part1(input: &Self::Input) -> Self::Output1 {
+    calibration_sum(&Scanner::new(&Vocabulary::numerals()), input)
+  }
+
+  fn part2(input: &Self::Input) -> Self::Output2 {
+    calibration_sum(&Scanner::new(&Vocabulary::english()), input)
+  }
+}
+
+/// Digits spelled out in English, from one to nine.
+pub const ENGLISH: [&str; 9] = [
+  "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Patterns standing for digits.
+#[derive(Clone, Debug, Default)]
+pub struct Vocabulary {
+  patterns: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+  /// The numerals `0` to `9`.
+  pub fn numerals() -> Self {
+    (0..10).fold(Self::default(), |vocabulary, digit| {
+      vocabulary.with(&digit.to_string(), digit)
+    })
+  }
+
+  /// Numerals along with digits spelled out in English.
+  pub fn english() -> Self {
+    Self::numerals().with_words(&ENGLISH)
+  }
+
+  /// Add `pattern`, standing for `value`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `pattern` is empty.
+  pub fn with(mut self, pattern: &str, value: u32) -> Self {
+    assert!(!pattern.is_empty(), "empty pattern for {value}");
+    self.patterns.push((pattern.to_owned(), value));
+    self
+  }
+
+  /// Add spelled out digits, `words[0]` standing for 1.
+  pub fn with_words(self, words: &[&str]) -> Self {
+    words
+      .iter()
+      .zip(1..)
+      .fold(self, |vocabulary, (word, value)| {
+        vocabulary.with(word, value)
+      })
+  }
+}
+
+/// Occurrence of a pattern in a line.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Match {
+  /// Byte offset of the pattern in the line.
+  pub offset: usize,
+  /// Length of the pattern, in bytes.
+  pub len: usize,
+  pub value: u32,
+}
+
+/// Transitions from a state of the automaton, on every byte.
+type Transitions = [usize; 256];
+
+/// Aho-Corasick automaton finding all the patterns of a [`Vocabulary`], overlapping or not, in a
+/// single pass over a line.
+#[derive(Clone, Debug)]
+pub struct Scanner {
+  /// Transitions of every state, the root being state 0.
+  next: Vec<Transitions>,
+
+  /// Patterns ending upon reaching every state, as `(length, value)`.
+  outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl Scanner {
+  pub fn new(vocabulary: &Vocabulary) -> Self {
+    // trie of the patterns, missing transitions being left to the root
+    let mut next = vec![[0; 256]];
+    let mut outputs = vec![Vec::new()];
+
+    for (pattern, value) in &vocabulary.patterns {
+      let mut state = 0;
+
+      for b in pattern.bytes() {
+        if next[state][b as usize] == 0 {
+          next[state][b as usize] = next.len();
+          next.push([0; 256]);
+          outputs.push(Vec::new());
+        }
+
+        state = next[state][b as usize];
+      }
+
+      outputs[state].push((pattern.len(), *value));
+    }
+
+    // breadth first, turn missing transitions into the ones of the longest proper suffix still in
+    // the trie, and collect the patterns ending there
+    let mut fail = vec![0; next.len()];
+    let mut queue: VecDeque<_> = next[0].iter().copied().filter(|&s| s != 0).collect();
+
+    while let Some(state) = queue.pop_front() {
+      let fallbacks = next[fail[state]];
+
+      for (child, fallback) in next[state].iter_mut().zip(fallbacks) {
+        if *child == 0 {
+          *child = fallback;
+        } else {
+          fail[*child] = fallback;
+          let inherited = outputs[fallback].clone();
+          outputs[*child].extend(inherited);
+          queue.push_back(*child);
+        }
+      }
+    }
+
+    Self { next, outputs }
+  }
+
+  /// All matches in `line`, by increasing end.
+  pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+    line
+      .bytes()
+      .enumerate()
+      .scan(0, |state, (i, b)| {
+        *state = self.next[*state][b as usize];
+        Some((i + 1, *state))
+      })
+      .flat_map(|(end, state)| {
+        self.outputs[state].iter().map(move |&(len, value)| Match {
+          offset: end - len,
+          len,
+          value,
+        })
+      })
+  }
+
+  /// Matches starting the first and the last in `line`, preferring the longest ones on ties.
+  pub fn first_last(&self, line: &str) -> Option<(Match, Match)> {
+    let mut matches = self.matches(line);
+    let (mut first, mut last) = matches.next().map(|m| (m, m))?;
+
+    for m in matches {
+      if (m.offset, Reverse(m.len)) < (first.offset, Reverse(first.len)) {
+        first = m;
+      }
+
+      if (m.offset, m.len) > (last.offset, last.len) {
+        last = m;
+      }
+    }
+
+    Some((first, last))
+  }
+}
+
+/// Calibration value of a line, made of its first and last digits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Calibration {
+  first: Match,
+  last: Match,
+  tokens: Vec<Match>,
+}
+
+impl Calibration {
+  pub fn value(&self) -> u32 {
+    self.first.value * 10 + self.last.value
+  }
+
+  pub fn first(&self) -> Match {
+    self.first
+  }
+
+  pub fn last(&self) -> Match {
+    self.last
+  }
+
+  /// All the digits found in the line, by offset.
+  pub fn tokens(&self) -> &[Match] {
+    &self.tokens
+  }
+}
+
+/// Error raised on a line without any digit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NoDigit {
+  /// Line number, starting at 1.
+  pub line: usize,
+}
+
+impl fmt::Display for NoDigit {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}: no digit", self.line)
+  }
+}
+
+impl Error for NoDigit {}
+
+/// Calibrate line number `number` with `scanner`.
+pub fn calibrate(scanner: &Scanner, number: usize, line: &str) -> Result<Calibration, NoDigit> {
+  let mut tokens: Vec<_> = scanner.matches(line).collect();
+  tokens.sort_by_key(|m| (m.offset, Reverse(m.len)));
+
+  let (&first, &Match { offset, .. }) = tokens
+    .first()
+    .zip(tokens.last())
+    .ok_or(NoDigit { line: number })?;
+  // longest of the matches starting the last, which come first among them
+  let last = tokens[tokens.partition_point(|m| m.offset < offset)];
+
+  Ok(Calibration {
+    first,
+    last,
+    tokens,
+  })
+}
+
+/// Calibrations of all `lines`, numbered from 1.
+pub fn calibrations<'a>(
+  scanner: &'a Scanner,
+  lines: &'a [String],
+) -> impl Iterator<Item = Result<Calibration, NoDigit>> + 'a {
+  lines
+    .iter()
+    .zip(1..)
+    .map(|(line, number)| calibrate(scanner, number, line))
+}
+
+/// Sum of the calibration values of all `lines`, skipping the ones without any digit.
+fn calibration_sum(scanner: &Scanner, lines: &[String]) -> u32 {
+  calibrations(scanner, lines)
+    .filter_map(Result::ok)
+    .map(|calibration| calibration.value())
+    .sum()
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::check;
+
+  use crate::{calibrate, calibrations, Day01, Match, NoDigit, Scanner, Vocabulary};
+
+  #[test]
+  fn examples() {
+    check::examples::<Day01>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day01>();
+  }
+
+  #[test]
+  fn overlaps() {
+    let scanner = Scanner::new(&Vocabulary::english());
+    let values = |line| {
+      let (first, last) = scanner.first_last(line).unwrap();
+      (first.value, last.value)
+    };
+
+    assert_eq!(values("eightwo"), (8, 2));
+    assert_eq!(values("twone"), (2, 1));
+    assert_eq!(values("xoneightx"), (1, 8));
+    assert_eq!(values("sevenine3"), (7, 3));
+    assert_eq!(values("5"), (5, 5));
+    assert_eq!(scanner.first_last("abc"), None);
+    assert_eq!(
+      scanner
+        .matches("oneight")
+        .map(|m| m.offset)
+        .collect::<Vec<_>>(),
+      [0, 2]
+    );
+  }
+
+  #[test]
+  fn vocabularies() {
+    let scanner = Scanner::new(&Vocabulary::numerals());
+    assert_eq!(
+      scanner
+        .first_last("one2three4five")
+        .map(|(f, l)| (f.value, l.value)),
+      Some((2, 4))
+    );
+
+    let french = Vocabulary::numerals().with_words(&[
+      "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+    ]);
+    let scanner = Scanner::new(&french);
+    assert_eq!(
+      scanner
+        .first_last("xdeuxneufx")
+        .map(|(f, l)| (f.value, l.value)),
+      Some((2, 9))
+    );
+
+    // a pattern inside a longer one starting earlier
+    let scanner = Scanner::new(&Vocabulary::default().with("abcd", 1).with("bc", 2));
+    assert_eq!(
+      scanner.first_last("abcd").map(|(f, l)| (f.value, l.value)),
+      Some((1, 2))
+    );
+  }
+
+  #[test]
+  fn diagnostics() {
+    let scanner = Scanner::new(&Vocabulary::english());
+    let calibration = calibrate(&scanner, 1, "xtwone3").unwrap();
+
+    assert_eq!(calibration.value(), 23);
+    assert_eq!(
+      calibration.tokens(),
+      [
+        Match {
+          offset: 1,
+          len: 3,
+          value: 2
+        },
+        Match {
+          offset: 3,
+          len: 3,
+          value: 1
+        },
+        Match {
+          offset: 6,
+          len: 1,
+          value: 3
+        },
+      ]
+    );
+
+    // the longest of the matches starting the last is the last digit
+    let scanner = Scanner::new(&Vocabulary::default().with("1", 1).with("12", 7));
+    let calibration = calibrate(&scanner, 1, "x1x12").unwrap();
+    assert_eq!(calibration.value(), 17);
+    assert_eq!(calibration.tokens().len(), 3);
+
+    let lines = ["1abc2", "pqr", "treb7uchet"].map(str::to_owned);
+    let scanner = Scanner::new(&Vocabulary::numerals());
+    let results: Vec<_> = calibrations(&scanner, &lines)
+      .map(|result| result.map(|calibration| calibration.value()))
+      .collect();
+
+    assert_eq!(results, [Ok(12), Err(NoDigit { line: 2 }), Ok(77)]);
+    assert_eq!(results[1].unwrap_err().to_string(), "line 2: no digit");
+  }
+}
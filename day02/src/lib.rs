@@ -0,0 +1,446 @@
+use std::{
+  collections::{BTreeMap, HashMap},
+  fmt,
+};
+
+use aoc_core::{
+  check::Example,
+  parse::{Lines, ParseError},
+  Solution,
+};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  type Input = Vec<Game>;
+  type Output1 = u32;
+  type Output2 = u32;
+
+  const DAY: u8 = 2;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("8")
+    .part2("2286")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    parse(input, Colours::Only(&COLOURS))
+  }
+
+  fn part1(input: &Self::Input) -> Self::Output1 {
+    part1(input, &Bag::standard())
+  }
+
+  fn part2(input: &Self::Input) -> Self::Output2 {
+    part2(input)
+  }
+}
+
+/// Colours of the standard game.
+pub const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// Colours accepted when parsing.
+#[derive(Clone, Copy, Debug)]
+pub enum Colours<'a> {
+  Any,
+  Only(&'a [&'a str]),
+}
+
+impl Colours<'_> {
+  fn accepts(self, colour: &str) -> bool {
+    match self {
+      Colours::Any => true,
+      Colours::Only(colours) => colours.contains(&colour),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct Game {
+  id: usize,
+  rounds: Vec<Round>,
+}
+
+impl Game {
+  pub fn id(&self) -> usize {
+    self.id
+  }
+
+  pub fn rounds(&self) -> &[Round] {
+    &self.rounds
+  }
+
+  /// Smallest bag this game could have been played with.
+  pub fn minimal_bag(&self) -> Bag {
+    self.explain_minimal_bag().into_bag()
+  }
+
+  /// Smallest bag this game could have been played with, along with the rounds forcing it.
+  pub fn explain_minimal_bag(&self) -> MinimalBag {
+    let mut minimal = MinimalBag::default();
+
+    for (i, round) in self.rounds.iter().enumerate() {
+      for (colour, count) in round.iter() {
+        if count > minimal.bag.count(colour) || !minimal.forced_by.contains_key(colour) {
+          minimal.bag.cubes.insert(colour.to_owned(), count);
+          minimal.forced_by.insert(colour.to_owned(), i);
+        }
+      }
+    }
+
+    minimal
+  }
+}
+
+/// Smallest bag a game could have been played with.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MinimalBag {
+  bag: Bag,
+
+  /// Index of the first round drawing the most cubes of every colour.
+  forced_by: BTreeMap<String, usize>,
+}
+
+impl MinimalBag {
+  pub fn bag(&self) -> &Bag {
+    &self.bag
+  }
+
+  pub fn into_bag(self) -> Bag {
+    self.bag
+  }
+
+  /// Index in [`Game::rounds`] of the first round drawing as many cubes of `colour` as the bag
+  /// holds, if any.
+  pub fn forced_by(&self, colour: &str) -> Option<usize> {
+    self.forced_by.get(colour).copied()
+  }
+}
+
+/// Colour limit of a bag broken by a round.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+  /// Index of the round in [`Game::rounds`].
+  pub round: usize,
+  pub colour: String,
+  pub count: u32,
+  pub limit: u32,
+}
+
+impl Violation {
+  /// Number of cubes drawn beyond the limit.
+  pub fn excess(&self) -> u32 {
+    self.count - self.limit
+  }
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "round {}: {} {} cubes, {} over the limit of {}",
+      self.round + 1,
+      self.count,
+      self.colour,
+      self.excess(),
+      self.limit
+    )
+  }
+}
+
+/// Cubes drawn in a round, by colour.
+#[derive(Debug, Default)]
+pub struct Round {
+  cubes: BTreeMap<String, u32>,
+}
+
+impl Round {
+  /// Number of cubes of `colour` drawn, 0 if none.
+  pub fn count(&self, colour: &str) -> u32 {
+    self.cubes.get(colour).copied().unwrap_or(0)
+  }
+
+  /// Colours drawn along with their counts, by colour.
+  pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+    self
+      .cubes
+      .iter()
+      .map(|(colour, &count)| (colour.as_str(), count))
+  }
+}
+
+/// Cubes in a bag, by colour.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bag {
+  cubes: BTreeMap<String, u32>,
+}
+
+impl Bag {
+  pub fn new<'a>(cubes: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+    Self {
+      cubes: cubes
+        .into_iter()
+        .map(|(colour, count)| (colour.to_owned(), count))
+        .collect(),
+    }
+  }
+
+  /// 12 red cubes, 13 green cubes and 14 blue cubes.
+  pub fn standard() -> Self {
+    Self::new(COLOURS.into_iter().zip([12, 13, 14]))
+  }
+
+  /// Number of cubes of `colour` in the bag, 0 if none.
+  pub fn count(&self, colour: &str) -> u32 {
+    self.cubes.get(colour).copied().unwrap_or(0)
+  }
+
+  /// Colours in the bag along with their counts, by colour.
+  pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+    self
+      .cubes
+      .iter()
+      .map(|(colour, &count)| (colour.as_str(), count))
+  }
+
+  /// Whether `round` could have been drawn from the bag.
+  pub fn allows(&self, round: &Round) -> bool {
+    round
+      .iter()
+      .all(|(colour, count)| count <= self.count(colour))
+  }
+
+  /// Limits of the bag broken by the rounds of `game`, round by round and colour by colour.
+  pub fn violations(&self, game: &Game) -> Vec<Violation> {
+    game
+      .rounds
+      .iter()
+      .enumerate()
+      .flat_map(|(i, round)| {
+        round.iter().filter_map(move |(colour, count)| {
+          let limit = self.count(colour);
+          (count > limit).then(|| Violation {
+            round: i,
+            colour: colour.to_owned(),
+            count,
+            limit,
+          })
+        })
+      })
+      .collect()
+  }
+
+  /// Product of the numbers of cubes of all `colours`.
+  pub fn power(&self, colours: &[&str]) -> u32 {
+    colours.iter().map(|colour| self.count(colour)).product()
+  }
+}
+
+/// Parse games, rejecting the colours `colours` does not accept.
+pub fn parse(input: &str, colours: Colours) -> Result<Vec<Game>, ParseError> {
+  let expected_colour = match colours {
+    Colours::Any => "a colour".to_owned(),
+    Colours::Only(colours) => format!("a colour ({})", colours.join(", ")),
+  };
+
+  // line declaring every game ID
+  let mut declared = HashMap::new();
+
+  Lines::new(Day02::DAY, input)
+    .map(|line| {
+      let (header, rounds) = line.split_once(line.text(), ':')?;
+      let id_token = line.strip_prefix(header, "Game ")?;
+      let id = line.parse(id_token, "a game ID")?;
+
+      line.unique(&mut declared, id, id_token, "a game ID")?;
+
+      let games = rounds
+        .split(';')
+        .map(|game| {
+          let mut round = Round::default();
+          for s in game.split(',') {
+            let mut iter = s.split_whitespace();
+            let count = iter
+              .next()
+              .ok_or_else(|| line.error_after(s, "a number of cubes"))?;
+            let n: u32 = line.parse(count, "a number of cubes")?;
+            let colour = iter
+              .next()
+              .ok_or_else(|| line.error_after(count, "a colour"))?;
+
+            if !colours.accepts(colour) {
+              return Err(line.error(colour, &expected_colour));
+            }
+
+            round.cubes.insert(colour.to_owned(), n);
+          }
+
+          Ok(round)
+        })
+        .collect::<Result<_, _>>()?;
+
+      Ok(Game { id, rounds: games })
+    })
+    .collect()
+}
+
+/// Game whose ID does not follow the one of the previous game.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnexpectedId {
+  /// Index of the game in the input.
+  pub index: usize,
+  pub expected: usize,
+  pub found: usize,
+}
+
+impl fmt::Display for UnexpectedId {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "game {} of the input has ID {}, expected {}",
+      self.index + 1,
+      self.found,
+      self.expected
+    )
+  }
+}
+
+/// Games whose IDs are not sequential, the first one being expected to be 1.
+///
+/// Meant to warn about filtered, reordered or concatenated inputs, which still parse fine.
+pub fn unexpected_ids(games: &[Game]) -> Vec<UnexpectedId> {
+  let expected = [1].into_iter().chain(games.iter().map(|game| game.id + 1));
+
+  games
+    .iter()
+    .zip(expected)
+    .enumerate()
+    .filter(|(_, (game, expected))| game.id != *expected)
+    .map(|(index, (game, expected))| UnexpectedId {
+      index,
+      expected,
+      found: game.id,
+    })
+    .collect()
+}
+
+/// Sum of the IDs of the games possible with `bag`.
+pub fn part1(games: &[Game], bag: &Bag) -> u32 {
+  games
+    .iter()
+    .filter(|game| game.rounds.iter().all(|round| bag.allows(round)))
+    .map(|game| game.id as u32)
+    .sum()
+}
+
+fn part2(games: &[Game]) -> u32 {
+  games
+    .iter()
+    .map(|game| game.minimal_bag().power(&COLOURS))
+    .sum()
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::{check, Solution};
+
+  use crate::{parse, part1, unexpected_ids, Bag, Colours, Day02, UnexpectedId, COLOURS};
+
+  #[test]
+  fn examples() {
+    check::examples::<Day02>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day02>();
+  }
+
+  #[test]
+  fn colours() {
+    let input = "Game 1: 3 red, 2 purple; 1 blue\nGame 2: 4 purple, 1 green";
+
+    let err = parse(input, Colours::Only(&COLOURS)).unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 18));
+    assert_eq!(err.expected(), "a colour (red, green, blue)");
+
+    let games = parse(input, Colours::Any).unwrap();
+    assert_eq!(games[0].rounds()[0].count("purple"), 2);
+    assert_eq!(games[0].rounds()[0].count("green"), 0);
+    assert_eq!(
+      games[1].minimal_bag(),
+      Bag::new([("green", 1), ("purple", 4)])
+    );
+
+    assert_eq!(part1(&games, &Bag::standard()), 0);
+    let bag = Bag::new([("red", 12), ("green", 13), ("blue", 14), ("purple", 3)]);
+    assert_eq!(part1(&games, &bag), 1);
+  }
+
+  #[test]
+  fn explanations() {
+    let games = Day02::parse(include_str!("example.txt")).unwrap();
+    let violations: Vec<_> = Bag::standard()
+      .violations(&games[2])
+      .iter()
+      .map(ToString::to_string)
+      .collect();
+
+    assert_eq!(
+      violations,
+      ["round 1: 20 red cubes, 8 over the limit of 12"]
+    );
+    assert!(Bag::standard().violations(&games[0]).is_empty());
+
+    let minimal = games[2].explain_minimal_bag();
+    assert_eq!(
+      minimal.bag(),
+      &Bag::new([("red", 20), ("green", 13), ("blue", 6)])
+    );
+    assert_eq!(minimal.forced_by("red"), Some(0));
+    assert_eq!(minimal.forced_by("green"), Some(1));
+    assert_eq!(minimal.forced_by("blue"), Some(0));
+    assert_eq!(minimal.forced_by("purple"), None);
+  }
+
+  #[test]
+  fn ids() {
+    let games = parse(
+      "Game 4: 1 red\nGame 2: 20 red\nGame 3: 1 blue",
+      Colours::Only(&COLOURS),
+    )
+    .unwrap();
+
+    assert_eq!(part1(&games, &Bag::standard()), 7);
+    assert_eq!(
+      unexpected_ids(&games),
+      [
+        UnexpectedId {
+          index: 0,
+          expected: 1,
+          found: 4
+        },
+        UnexpectedId {
+          index: 1,
+          expected: 5,
+          found: 2
+        },
+      ]
+    );
+    assert_eq!(
+      unexpected_ids(&games)[0].to_string(),
+      "game 1 of the input has ID 4, expected 1"
+    );
+
+    let err = parse("Game 1: 1 red\nGame 1: 2 red", Colours::Any).unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 6));
+    assert_eq!(err.expected(), "a game ID other than the one on line 1");
+
+    let err = parse("Game x: 1 red", Colours::Any).unwrap_err();
+    assert_eq!(
+      (err.line(), err.column(), err.expected()),
+      (1, 6, "a game ID")
+    );
+
+    let err = parse("Gane 1: 1 red", Colours::Any).unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 3));
+  }
+}
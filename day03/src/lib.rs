@@ -0,0 +1,597 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  error::Error,
+  fmt,
+  io::{self, BufRead},
+  ops::Range,
+};
+
+use aoc_core::{
+  check::Example,
+  grid::{Grid, Pos},
+  parse::{Line, Lines, ParseError},
+  Solution,
+};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+  type Input = SchematicIndex;
+  type Output1 = u64;
+  type Output2 = usize;
+
+  const DAY: u8 = 3;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("4361")
+    .part2("467835")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    SchematicIndex::new(&parse(input)?).map_err(|err| {
+      let line = Lines::new(Day03::DAY, input).nth(err.row).unwrap();
+      let text = line.text();
+      let offset = |col| text.char_indices().nth(col).map_or(text.len(), |(i, _)| i);
+      line.error(
+        &text[offset(err.cols.start)..offset(err.cols.end)],
+        NUMBER_EXPECTED,
+      )
+    })
+  }
+
+  fn part1(index: &Self::Input) -> Self::Output1 {
+    solve(index, &GearRule::default()).0
+  }
+
+  fn part2(index: &Self::Input) -> Self::Output2 {
+    solve(index, &GearRule::default()).1
+  }
+}
+
+pub type Schematic = Grid<char>;
+
+fn parse(input: &str) -> Result<Schematic, ParseError> {
+  Grid::parse(Day03::DAY, input, "a schematic cell", Some)
+}
+
+fn is_symbol(c: char) -> bool {
+  !(c == '.' || c.is_ascii_digit())
+}
+
+/// Number of a schematic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Number {
+  pub row: usize,
+  /// Columns spanned by the digits.
+  pub cols: Range<usize>,
+  pub value: u64,
+}
+
+impl Number {
+  /// Positions around the number, including diagonals, which may lie outside of the schematic.
+  fn surroundings(&self) -> impl Iterator<Item = Pos> + '_ {
+    let cols = self.cols.start.saturating_sub(1)..self.cols.end + 1;
+
+    (self.row.saturating_sub(1)..self.row + 2)
+      .flat_map(move |row| cols.clone().map(move |col| Pos::new(row, col)))
+      .filter(|pos| pos.row != self.row || !self.cols.contains(&pos.col))
+  }
+}
+
+/// What a number of a schematic is expected to be.
+const NUMBER_EXPECTED: &str = "a number below 2^64";
+
+/// Error raised on a number of a schematic too large for a `u64`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NumberOverflow {
+  pub row: usize,
+  /// Columns spanned by the digits.
+  pub cols: Range<usize>,
+}
+
+impl fmt::Display for NumberOverflow {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "number on row {}, columns {}..{}, too large",
+      self.row, self.cols.start, self.cols.end
+    )
+  }
+}
+
+impl Error for NumberOverflow {}
+
+/// Numbers of row `row`, out of the digit in every cell, if any.
+fn row_numbers(
+  row: usize,
+  digits: impl Iterator<Item = Option<u32>>,
+) -> Result<Vec<Number>, NumberOverflow> {
+  // digits of every number, along with its value unless it overflowed
+  let mut numbers = Vec::new();
+  let mut current: Option<(Range<usize>, Option<u64>)> = None;
+
+  for (col, digit) in digits.enumerate() {
+    match (digit, &mut current) {
+      (Some(digit), Some((cols, value))) => {
+        *value = value.and_then(|value| value.checked_mul(10)?.checked_add(digit.into()));
+        cols.end = col + 1;
+      }
+      (Some(digit), None) => current = Some((col..col + 1, Some(digit.into()))),
+      (None, _) => numbers.extend(current.take()),
+    }
+  }
+
+  numbers.extend(current);
+  numbers
+    .into_iter()
+    .map(|(cols, value)| match value {
+      Some(value) => Ok(Number { row, cols, value }),
+      None => Err(NumberOverflow { row, cols }),
+    })
+    .collect()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Symbol {
+  pub pos: Pos,
+  pub symbol: char,
+}
+
+/// Numbers and symbols of a schematic, along with which ones are adjacent, diagonals included.
+///
+/// Numbers and symbols are listed row by row, and referred to by their index in these lists.
+#[derive(Clone, Debug)]
+pub struct SchematicIndex {
+  numbers: Vec<Number>,
+  symbols: Vec<Symbol>,
+
+  /// Symbols adjacent to every number.
+  number_symbols: Vec<Vec<usize>>,
+
+  /// Numbers adjacent to every symbol.
+  symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl SchematicIndex {
+  /// Index of `schematic`, failing on numbers too large for a `u64`.
+  pub fn new(schematic: &Schematic) -> Result<Self, NumberOverflow> {
+    let numbers: Vec<_> = schematic
+      .rows()
+      .enumerate()
+      .map(|(row, line)| row_numbers(row, line.iter().map(|c| c.to_digit(10))))
+      .collect::<Result<Vec<_>, _>>()?
+      .into_iter()
+      .flatten()
+      .collect();
+
+    let symbols: Vec<_> = schematic
+      .iter()
+      .filter(|(_, &c)| is_symbol(c))
+      .map(|(pos, &symbol)| Symbol { pos, symbol })
+      .collect();
+    let symbol_at: HashMap<_, _> = symbols
+      .iter()
+      .enumerate()
+      .map(|(i, symbol)| (symbol.pos, i))
+      .collect();
+
+    let number_symbols: Vec<Vec<_>> = numbers
+      .iter()
+      .map(|number| {
+        number
+          .surroundings()
+          .filter_map(|pos| symbol_at.get(&pos).copied())
+          .collect()
+      })
+      .collect();
+
+    let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+
+    for (number, near) in number_symbols.iter().enumerate() {
+      for &symbol in near {
+        symbol_numbers[symbol].push(number);
+      }
+    }
+
+    Ok(Self {
+      numbers,
+      symbols,
+      number_symbols,
+      symbol_numbers,
+    })
+  }
+
+  pub fn numbers(&self) -> &[Number] {
+    &self.numbers
+  }
+
+  pub fn symbols(&self) -> &[Symbol] {
+    &self.symbols
+  }
+
+  /// Symbols adjacent to the number of index `number`.
+  pub fn symbols_near(&self, number: usize) -> impl Iterator<Item = &Symbol> + '_ {
+    self.number_symbols[number]
+      .iter()
+      .map(|&symbol| &self.symbols[symbol])
+  }
+
+  /// Numbers adjacent to the symbol of index `symbol`.
+  pub fn numbers_near(&self, symbol: usize) -> impl Iterator<Item = &Number> + '_ {
+    self.symbol_numbers[symbol]
+      .iter()
+      .map(|&number| &self.numbers[number])
+  }
+
+  /// Numbers adjacent to at least one symbol.
+  pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+    self
+      .numbers
+      .iter()
+      .zip(&self.number_symbols)
+      .filter(|(_, near)| !near.is_empty())
+      .map(|(number, _)| number)
+  }
+}
+
+/// Error raised while streaming a schematic.
+#[derive(Debug)]
+pub enum StreamError {
+  Io(io::Error),
+  Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      StreamError::Io(err) => write!(f, "cannot read schematic: {err}"),
+      StreamError::Parse(err) => err.fmt(f),
+    }
+  }
+}
+
+impl Error for StreamError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      StreamError::Io(err) => Some(err),
+      StreamError::Parse(err) => Some(err),
+    }
+  }
+}
+
+impl From<io::Error> for StreamError {
+  fn from(err: io::Error) -> Self {
+    StreamError::Io(err)
+  }
+}
+
+impl From<ParseError> for StreamError {
+  fn from(err: ParseError) -> Self {
+    StreamError::Parse(err)
+  }
+}
+
+/// Row of a streamed schematic, one byte per cell.
+struct Row {
+  cells: Vec<u8>,
+  numbers: Vec<Number>,
+}
+
+/// Totals of both parts over the row `window[middle]`, `window` holding it along with the rows
+/// around it.
+fn window_totals(window: &VecDeque<Row>, middle: usize, rule: &GearRule) -> (u64, usize) {
+  let row = &window[middle];
+  let near = |cols: Range<usize>| {
+    window.iter().flat_map(move |row| {
+      let end = cols.end.min(row.cells.len());
+      row.cells[cols.start.min(end)..end].iter().copied()
+    })
+  };
+
+  let part1 = row
+    .numbers
+    .iter()
+    .filter(|number| {
+      near(number.cols.start.saturating_sub(1)..number.cols.end + 1).any(|b| is_symbol(b as char))
+    })
+    .map(|number| number.value)
+    .sum();
+
+  let part2 = row
+    .cells
+    .iter()
+    .enumerate()
+    .filter(|(_, &b)| is_symbol(b as char))
+    .map(|(col, &b)| {
+      let near: Vec<_> = window
+        .iter()
+        .flat_map(|row| &row.numbers)
+        .filter(|number| number.cols.start <= col + 1 && number.cols.end >= col)
+        .map(|number| number.value)
+        .collect();
+      (b as char, near)
+    })
+    .filter(|(symbol, near)| rule.is_gear(*symbol, near.len()))
+    .map(|(_, near)| rule.ratio(near.into_iter()))
+    .sum();
+
+  (part1, part2)
+}
+
+/// Totals of both parts over a schematic read row by row from `reader`, only keeping three rows in
+/// memory at once.
+///
+/// Cells must be ASCII, so that they take a single byte.
+pub fn stream(reader: impl BufRead, rule: &GearRule) -> Result<(u64, usize), StreamError> {
+  let mut window = VecDeque::with_capacity(3);
+  let mut width = None;
+  let mut totals = (0, 0);
+  let mut add = |(part1, part2)| {
+    totals.0 += part1;
+    totals.1 += part2;
+  };
+
+  for (row, text) in reader.lines().enumerate() {
+    let text = text?;
+    let line = Line::new(Day03::DAY, row + 1, &text);
+
+    if let Some(i) = text.find(|c: char| !c.is_ascii()) {
+      return Err(line.error(&text[i..], "an ASCII schematic cell").into());
+    }
+
+    match width {
+      None => width = Some(text.len()),
+      Some(width) if width != text.len() => {
+        let at = &text[width.min(text.len())..];
+        return Err(line.error(at, &format!("a row of {width} cells")).into());
+      }
+      _ => (),
+    }
+
+    let numbers = row_numbers(row, text.chars().map(|c| c.to_digit(10)))
+      .map_err(|err| line.error(&text[err.cols], NUMBER_EXPECTED))?;
+    let cells = text.into_bytes();
+    window.push_back(Row { cells, numbers });
+
+    // the previous row now has all its neighbours
+    if window.len() > 1 {
+      add(window_totals(&window, window.len() - 2, rule));
+    }
+
+    if window.len() == 3 {
+      window.pop_front();
+    }
+  }
+
+  if !window.is_empty() {
+    add(window_totals(&window, window.len() - 1, rule));
+  }
+
+  Ok(totals)
+}
+
+/// Numbers of adjacent numbers making a symbol a gear.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Neighbours {
+  Exactly(usize),
+  AtLeast(usize),
+}
+
+/// Way the numbers adjacent to a gear make up its ratio.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Combine {
+  Product,
+  Sum,
+  Max,
+}
+
+/// Rule telling gears apart from other symbols, and how to compute their ratios.
+///
+/// The default rule is the one of the puzzle: a `*` adjacent to exactly two numbers, whose ratio is
+/// their product.
+#[derive(Clone, Debug)]
+pub struct GearRule {
+  /// Symbols which may be gears.
+  pub symbols: String,
+  pub neighbours: Neighbours,
+  pub combine: Combine,
+}
+
+impl Default for GearRule {
+  fn default() -> Self {
+    Self {
+      symbols: "*".to_owned(),
+      neighbours: Neighbours::Exactly(2),
+      combine: Combine::Product,
+    }
+  }
+}
+
+impl GearRule {
+  /// Whether `symbol`, adjacent to `neighbours` numbers, is a gear.
+  pub fn is_gear(&self, symbol: char, neighbours: usize) -> bool {
+    self.symbols.contains(symbol)
+      && match self.neighbours {
+        Neighbours::Exactly(n) => neighbours == n,
+        Neighbours::AtLeast(n) => neighbours >= n,
+      }
+  }
+
+  /// Ratio of a gear adjacent to numbers of values `values`.
+  pub fn ratio(&self, values: impl Iterator<Item = u64>) -> usize {
+    let values = values.map(|value| value as usize);
+
+    match self.combine {
+      Combine::Product => values.product(),
+      Combine::Sum => values.sum(),
+      Combine::Max => values.max().unwrap_or(0),
+    }
+  }
+}
+
+/// Sum of the part numbers and sum of the ratios of the gears according to `rule`.
+pub fn solve(index: &SchematicIndex, rule: &GearRule) -> (u64, usize) {
+  let part1 = index.part_numbers().map(|number| number.value).sum();
+  let part2 = index
+    .symbols()
+    .iter()
+    .enumerate()
+    .filter(|(i, symbol)| rule.is_gear(symbol.symbol, index.symbol_numbers[*i].len()))
+    .map(|(i, _)| rule.ratio(index.numbers_near(i).map(|number| number.value)))
+    .sum();
+
+  (part1, part2)
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::{
+    check::{self, rng},
+    grid::{Grid, Pos},
+    Solution,
+  };
+
+  use crate::{
+    solve, stream, Combine, Day03, GearRule, Neighbours, Number, SchematicIndex, StreamError,
+  };
+
+  #[test]
+  fn examples() {
+    check::examples::<Day03>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day03>();
+  }
+
+  #[test]
+  fn index() {
+    let index = Day03::parse(include_str!("example.txt")).unwrap();
+
+    assert_eq!(index.numbers().len(), 10);
+    assert_eq!(
+      index.numbers()[0],
+      Number {
+        row: 0,
+        cols: 0..3,
+        value: 467
+      }
+    );
+    assert_eq!(index.symbols().len(), 6);
+    assert_eq!(index.symbols()[0].pos, Pos::new(1, 3));
+
+    let near: Vec<_> = index.symbols_near(0).map(|symbol| symbol.symbol).collect();
+    assert_eq!(near, ['*']);
+    let near: Vec<_> = index.numbers_near(0).map(|number| number.value).collect();
+    assert_eq!(near, [467, 35]);
+
+    // sum of the numbers touching a '#'
+    let sum: u64 = (0..index.numbers().len())
+      .filter(|&i| index.symbols_near(i).any(|symbol| symbol.symbol == '#'))
+      .map(|i| index.numbers()[i].value)
+      .sum();
+    assert_eq!(sum, 633);
+  }
+
+  #[test]
+  fn streaming() {
+    let input = include_str!("example.txt");
+    let rule = GearRule::default();
+    assert_eq!(stream(input.as_bytes(), &rule).unwrap(), (4361, 467835));
+    assert_eq!(stream(&b""[..], &rule).unwrap(), (0, 0));
+
+    let mut next = rng(3);
+
+    let rules = [
+      rule,
+      GearRule {
+        symbols: "*#".to_owned(),
+        neighbours: Neighbours::AtLeast(1),
+        combine: Combine::Sum,
+      },
+      GearRule {
+        symbols: "#".to_owned(),
+        neighbours: Neighbours::Exactly(3),
+        combine: Combine::Max,
+      },
+    ];
+
+    for i in 0..300 {
+      let rule = &rules[i % rules.len()];
+      let width = 1 + next() as usize % 12;
+      let height = 1 + next() as usize % 12;
+      let cells = (0..width * height)
+        .map(|_| match next() % 10 {
+          0..=3 => '.',
+          4 => '*',
+          5 => '#',
+          _ => char::from(b'0' + (next() % 10) as u8),
+        })
+        .collect();
+      let schematic = Grid::new(width, height, cells);
+      let text = schematic.to_string();
+
+      assert_eq!(
+        stream(text.as_bytes(), rule).unwrap(),
+        solve(&SchematicIndex::new(&schematic).unwrap(), rule),
+        "{text}"
+      );
+    }
+
+    let Err(StreamError::Parse(err)) = stream(&b"..1\n.*\n"[..], &rules[0]) else {
+      panic!("ragged schematic accepted");
+    };
+    assert_eq!((err.line(), err.column()), (2, 3));
+    assert_eq!(err.expected(), "a row of 3 cells");
+  }
+
+  #[test]
+  fn large_numbers() {
+    let rule = GearRule::default();
+    let input = "9999999999.\n*..........\n";
+    let index = Day03::parse(input).unwrap();
+    assert_eq!(solve(&index, &rule).0, 9_999_999_999);
+    assert_eq!(stream(input.as_bytes(), &rule).unwrap().0, 9_999_999_999);
+
+    let max = u64::MAX.to_string();
+    let input = format!("{max}.\n*{}\n", ".".repeat(max.len()));
+    let index = Day03::parse(&input).unwrap();
+    assert_eq!(solve(&index, &rule).0, u64::MAX);
+
+    // one past the largest value, after a non-ASCII cell shifting bytes from cells
+    let input = format!("é.18446744073709551616\n*{}\n", ".".repeat(21));
+    let err = Day03::parse(&input).unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 4));
+    assert_eq!(err.found(), "\"18446744073709551616\"");
+    assert_eq!(err.expected(), "a number below 2^64");
+
+    let Err(StreamError::Parse(err)) = stream(&input.as_bytes()[2..], &rule) else {
+      panic!("overflowing number accepted");
+    };
+    assert_eq!((err.line(), err.column()), (1, 2));
+  }
+
+  #[test]
+  fn gear_rules() {
+    let index = Day03::parse(include_str!("example.txt")).unwrap();
+    let rule = |symbols: &str, neighbours, combine| GearRule {
+      symbols: symbols.to_owned(),
+      neighbours,
+      combine,
+    };
+
+    assert_eq!(solve(&index, &GearRule::default()).1, 467835);
+    // any '*', alone or not
+    assert_eq!(
+      solve(&index, &rule("*", Neighbours::AtLeast(1), Combine::Sum)).1,
+      467 + 35 + 617 + 755 + 598
+    );
+    assert_eq!(
+      solve(&index, &rule("#$", Neighbours::Exactly(1), Combine::Max)).1,
+      633 + 664
+    );
+    assert_eq!(
+      solve(&index, &rule("+", Neighbours::Exactly(2), Combine::Product)).1,
+      0
+    );
+  }
+}
@@ -0,0 +1,430 @@
+use std::{
+  collections::{BTreeSet, HashMap},
+  error::Error,
+  fmt,
+};
+
+use aoc_core::{
+  check::Example,
+  parse::{Lines, ParseError},
+  Solution,
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  type Input = ScratchcardTable;
+  type Output1 = u64;
+  type Output2 = usize;
+
+  const DAY: u8 = 4;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("13")
+    .part2("30")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    parse(input)
+  }
+
+  fn part1(table: &Self::Input) -> Self::Output1 {
+    table.points()
+  }
+
+  fn part2(table: &Self::Input) -> Self::Output2 {
+    table
+      .cascade(Overflow::Clamp)
+      .expect("clamped cascade")
+      .total()
+  }
+}
+
+/// Numbers below this are stored as bits in a [`NumberSet`].
+const SMALL: u32 = 128;
+
+/// Set of numbers, stored as a bitset for numbers below 128 and as a sorted set for the others.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NumberSet {
+  bits: [u64; SMALL as usize / 64],
+  others: BTreeSet<u32>,
+}
+
+impl NumberSet {
+  /// Add `n`, returning whether it was not in the set yet.
+  pub fn insert(&mut self, n: u32) -> bool {
+    if n < SMALL {
+      let (word, bit) = ((n / 64) as usize, 1 << (n % 64));
+      let new = self.bits[word] & bit == 0;
+      self.bits[word] |= bit;
+      new
+    } else {
+      self.others.insert(n)
+    }
+  }
+
+  pub fn contains(&self, n: u32) -> bool {
+    if n < SMALL {
+      self.bits[(n / 64) as usize] & (1 << (n % 64)) != 0
+    } else {
+      self.others.contains(&n)
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self
+      .bits
+      .iter()
+      .map(|word| word.count_ones() as usize)
+      .sum::<usize>()
+      + self.others.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Number of numbers in both sets.
+  pub fn common(&self, other: &NumberSet) -> usize {
+    let small: u32 = self
+      .bits
+      .iter()
+      .zip(&other.bits)
+      .map(|(a, b)| (a & b).count_ones())
+      .sum();
+
+    small as usize + self.others.intersection(&other.others).count()
+  }
+}
+
+impl FromIterator<u32> for NumberSet {
+  fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+    let mut set = Self::default();
+
+    for n in iter {
+      set.insert(n);
+    }
+
+    set
+  }
+}
+
+#[derive(Debug)]
+pub struct Card {
+  id: u32,
+  winning: NumberSet,
+  ours: NumberSet,
+}
+
+impl Card {
+  pub fn id(&self) -> u32 {
+    self.id
+  }
+
+  /// Number of our numbers which are winning.
+  pub fn matches(&self) -> usize {
+    self.winning.common(&self.ours)
+  }
+
+  /// Points of the card, saturating at `u64::MAX` past 64 matches.
+  pub fn points(&self) -> u64 {
+    match self.matches() {
+      0 => 0,
+      n => u32::try_from(n - 1)
+        .ok()
+        .and_then(|shift| 1u64.checked_shl(shift))
+        .unwrap_or(u64::MAX),
+    }
+  }
+}
+
+/// What to do with copies won of cards past the end of the table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Overflow {
+  /// Ignore them.
+  Clamp,
+  /// Fail with a [`CopyOverflow`].
+  Report,
+}
+
+/// Error raised when a card wins a copy of a card which is not in the table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CopyOverflow {
+  pub card: u32,
+  /// ID of the missing card, which may be past the largest ID.
+  pub missing: u64,
+}
+
+impl fmt::Display for CopyOverflow {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "card {} wins a copy of card {}, which is not in the table",
+      self.card, self.missing
+    )
+  }
+}
+
+impl Error for CopyOverflow {}
+
+/// Copies of a card at the end of a cascade.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CardCopies {
+  pub id: u32,
+  /// All copies, the original included.
+  pub copies: usize,
+  /// Copies won from other cards, as `(card ID, copies)`.
+  pub won_from: Vec<(u32, usize)>,
+}
+
+/// Copies of every card once all of them have been scratched, by card ID.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cascade {
+  cards: Vec<CardCopies>,
+}
+
+impl Cascade {
+  pub fn cards(&self) -> &[CardCopies] {
+    &self.cards
+  }
+
+  /// Number of cards in the end, originals included.
+  pub fn total(&self) -> usize {
+    self.cards.iter().map(|card| card.copies).sum()
+  }
+}
+
+impl fmt::Display for Cascade {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for card in &self.cards {
+      write!(f, "card {}: {} copies (original", card.id, card.copies)?;
+
+      for (id, copies) in &card.won_from {
+        write!(f, ", {copies} from card {id}")?;
+      }
+
+      writeln!(f, ")")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Scratchcards, each one winning copies of the cards of the next IDs.
+#[derive(Debug)]
+pub struct ScratchcardTable {
+  /// Cards, by ID.
+  cards: Vec<Card>,
+}
+
+impl ScratchcardTable {
+  /// Table of `cards`, which must have distinct IDs.
+  pub fn new(mut cards: Vec<Card>) -> Self {
+    cards.sort_by_key(|card| card.id);
+    Self { cards }
+  }
+
+  /// Cards, by ID.
+  pub fn cards(&self) -> &[Card] {
+    &self.cards
+  }
+
+  /// Points of all the cards, saturating at `u64::MAX`.
+  pub fn points(&self) -> u64 {
+    self
+      .cards
+      .iter()
+      .map(Card::points)
+      .fold(0, u64::saturating_add)
+  }
+
+  /// Scratch all the cards, dealing with copies won past the end of the table as `overflow` says.
+  pub fn cascade(&self, overflow: Overflow) -> Result<Cascade, CopyOverflow> {
+    let mut cards: Vec<_> = self
+      .cards
+      .iter()
+      .map(|card| CardCopies {
+        id: card.id,
+        copies: 1,
+        won_from: Vec::new(),
+      })
+      .collect();
+
+    // cards are sorted by ID, so that copies are only ever won of cards not scratched yet
+    for (i, card) in self.cards.iter().enumerate() {
+      let copies = cards[i].copies;
+
+      for n in 1..=card.matches() as u64 {
+        // IDs past the largest one are never in the table
+        let won = u32::try_from(card.id as u64 + n).ok();
+
+        match won.and_then(|won| self.cards.binary_search_by_key(&won, |card| card.id).ok()) {
+          Some(j) => {
+            cards[j].copies += copies;
+            cards[j].won_from.push((card.id, copies));
+          }
+          None if overflow == Overflow::Clamp => (),
+          None => {
+            return Err(CopyOverflow {
+              card: card.id,
+              missing: card.id as u64 + n,
+            })
+          }
+        }
+      }
+    }
+
+    Ok(Cascade { cards })
+  }
+}
+
+fn parse(input: &str) -> Result<ScratchcardTable, ParseError> {
+  // line declaring every card ID
+  let mut declared = HashMap::new();
+
+  let cards = Lines::new(Day04::DAY, input)
+    .map(|l| {
+      let (header, numbers) = l.split_once(l.text(), ':')?;
+      let id_token = l.strip_prefix(header, "Card")?.trim_start();
+      let id = l.parse(id_token, "a card ID")?;
+
+      l.unique(&mut declared, id, id_token, "a card ID")?;
+
+      let (winning, ours) = l.split_once(numbers, '|')?;
+      let numbers = |part: &str| -> Result<NumberSet, _> {
+        part
+          .split_ascii_whitespace()
+          .map(|n| l.parse(n, "a number"))
+          .collect()
+      };
+
+      Ok(Card {
+        id,
+        winning: numbers(winning)?,
+        ours: numbers(ours)?,
+      })
+    })
+    .collect::<Result<_, _>>()?;
+
+  Ok(ScratchcardTable::new(cards))
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::HashSet;
+
+  use aoc_core::{
+    check::{self, rng},
+    Solution,
+  };
+
+  use crate::{CopyOverflow, Day04, NumberSet, Overflow};
+
+  #[test]
+  fn examples() {
+    check::examples::<Day04>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day04>();
+  }
+
+  #[test]
+  fn cascade() {
+    let table = Day04::parse(include_str!("example.txt")).unwrap();
+    let cascade = table.cascade(Overflow::Report).unwrap();
+
+    assert_eq!(cascade.total(), 30);
+    assert_eq!(cascade.cards()[3].copies, 8);
+    assert_eq!(cascade.cards()[3].won_from, [(1, 1), (2, 2), (3, 4)]);
+    assert_eq!(
+      cascade.to_string().lines().nth(2),
+      Some("card 3: 4 copies (original, 1 from card 1, 2 from card 2)")
+    );
+
+    // the last card wins a copy of a card past the end
+    let table = Day04::parse("Card 2: 1 2 | 1 3\nCard 1: 5 | 5").unwrap();
+    assert_eq!(table.cascade(Overflow::Clamp).unwrap().total(), 3);
+    assert_eq!(
+      table.cascade(Overflow::Report),
+      Err(CopyOverflow {
+        card: 2,
+        missing: 3
+      })
+    );
+
+    let err = Day04::parse("Card 1: 1 | 1\nCard  1: 2 | 2").unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 7));
+
+    // cards win copies past the largest ID
+    let max = u32::MAX;
+    let table = Day04::parse(&format!("Card {}: 1 2 | 1 2\nCard {max}: 3 | 3", max - 1)).unwrap();
+    assert_eq!(table.cascade(Overflow::Clamp).unwrap().total(), 3);
+    assert_eq!(
+      table.cascade(Overflow::Report),
+      Err(CopyOverflow {
+        card: max - 1,
+        missing: max as u64 + 1
+      })
+    );
+  }
+
+  #[test]
+  fn points() {
+    let card = |matches: u32| {
+      let numbers: Vec<_> = (0..matches).map(|n| n.to_string()).collect();
+      let numbers = numbers.join(" ");
+      format!("Card {}: {numbers} | {numbers}", matches + 1)
+    };
+    let points = |matches| Day04::part1(&Day04::parse(&card(matches)).unwrap());
+
+    assert_eq!(points(0), 0);
+    assert_eq!(points(1), 1);
+    assert_eq!(points(33), 1 << 32);
+    assert_eq!(points(64), 1 << 63);
+    assert_eq!(points(65), u64::MAX);
+
+    let table = [40, 70, 200].map(card).join("\n");
+    assert_eq!(Day04::part1(&Day04::parse(&table).unwrap()), u64::MAX);
+  }
+
+  /// Random number, mostly small but sometimes past the bitset.
+  fn number(next: &mut impl FnMut() -> u64) -> u32 {
+    match next() % 8 {
+      0 => 128 + (next() % 64) as u32,
+      1 => next() as u32,
+      _ => (next() % 128) as u32,
+    }
+  }
+
+  #[test]
+  fn number_sets() {
+    let mut next = rng(4);
+
+    for _ in 0..500 {
+      let len = next() % 30;
+      let a: Vec<_> = (0..len).map(|_| number(&mut next)).collect();
+      let len = next() % 30;
+      let b: Vec<_> = (0..len).map(|_| number(&mut next)).collect();
+
+      let set_a: NumberSet = a.iter().copied().collect();
+      let set_b: NumberSet = b.iter().copied().collect();
+      let hash_a: HashSet<_> = a.iter().copied().collect();
+      let hash_b: HashSet<_> = b.iter().copied().collect();
+
+      assert_eq!(set_a.len(), hash_a.len());
+      assert_eq!(
+        set_a.common(&set_b),
+        hash_a.intersection(&hash_b).count(),
+        "{a:?} {b:?}"
+      );
+      assert!(b.iter().all(|&n| set_a.contains(n) == hash_a.contains(&n)));
+    }
+
+    let mut set = NumberSet::default();
+    assert!(set.is_empty());
+    assert!(set.insert(127) && set.insert(128) && set.insert(0));
+    assert!(!set.insert(127) && !set.insert(128));
+    assert!(!set.contains(1) && !set.contains(129));
+    assert_eq!(set.len(), 3);
+  }
+}
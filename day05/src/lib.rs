@@ -0,0 +1,1144 @@
+use std::{
+  collections::{HashMap, HashSet},
+  error::Error,
+  fmt,
+};
+
+use aoc_core::{
+  check::Example,
+  parse::{Lines, ParseError},
+  Solution,
+};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  type Input = SeedAlmanac;
+  type Output1 = u64;
+  type Output2 = u64;
+
+  const DAY: u8 = 5;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("35")
+    .part2("46")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    SeedAlmanac::parse(input)
+  }
+
+  fn part1(input: &Self::Input) -> Self::Output1 {
+    part1(input)
+  }
+
+  fn part2(input: &Self::Input) -> Self::Output2 {
+    part2(input)
+  }
+}
+
+/// Inclusive range of values.
+pub type Range = (u64, u64);
+
+/// Set of values, as sorted, disjoint and non-adjacent inclusive ranges.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+  ranges: Vec<Range>,
+}
+
+impl IntervalSet {
+  /// Set of all the values in `ranges`, which may overlap. Ranges whose start is past their end
+  /// are ignored.
+  pub fn new(ranges: impl IntoIterator<Item = Range>) -> Self {
+    let mut ranges: Vec<_> = ranges.into_iter().filter(|(a, b)| a <= b).collect();
+    ranges.sort_unstable();
+
+    let mut normalised: Vec<Range> = Vec::with_capacity(ranges.len());
+
+    for (a, b) in ranges {
+      match normalised.last_mut() {
+        Some(last) if a <= last.1.saturating_add(1) => last.1 = last.1.max(b),
+        _ => normalised.push((a, b)),
+      }
+    }
+
+    Self { ranges: normalised }
+  }
+
+  pub fn ranges(&self) -> &[Range] {
+    &self.ranges
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  pub fn min(&self) -> Option<u64> {
+    self.ranges.first().map(|(a, _)| *a)
+  }
+
+  pub fn contains(&self, value: u64) -> bool {
+    let i = self.ranges.partition_point(|(_, b)| *b < value);
+    self.ranges.get(i).is_some_and(|(a, _)| *a <= value)
+  }
+
+  pub fn union(&self, other: &IntervalSet) -> Self {
+    Self::new(self.ranges.iter().chain(&other.ranges).copied())
+  }
+
+  pub fn intersection(&self, other: &IntervalSet) -> Self {
+    self.difference(&self.difference(other))
+  }
+
+  /// Values of `self` which are not in `other`.
+  pub fn difference(&self, other: &IntervalSet) -> Self {
+    let mut ranges = Vec::new();
+
+    for &(a, b) in &self.ranges {
+      let mut start = Some(a);
+
+      for &(c, d) in &other.ranges {
+        let Some(from) = start else { break };
+
+        if d < from {
+          continue;
+        }
+
+        if c > b {
+          break;
+        }
+
+        if c > from {
+          ranges.push((from, c - 1));
+        }
+
+        start = (d < b).then(|| d + 1);
+      }
+
+      ranges.extend(start.map(|from| (from, b)));
+    }
+
+    Self { ranges }
+  }
+}
+
+/// Error raised on a rule which does not map any value, or maps values past `u64::MAX`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CatMapError {
+  Empty,
+  Overflow,
+}
+
+impl fmt::Display for CatMapError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      CatMapError::Empty => f.write_str("empty range"),
+      CatMapError::Overflow => f.write_str("range past the largest value"),
+    }
+  }
+}
+
+impl Error for CatMapError {}
+
+/// Rule mapping `src..=src_end` to `dest..=dest_end`.
+#[derive(Debug)]
+struct CatMap {
+  src: u64,
+  src_end: u64,
+  dest: u64,
+  dest_end: u64,
+}
+
+impl CatMap {
+  /// Rule mapping the `range` values starting at `src` to the ones starting at `dest`.
+  fn new(src: u64, dest: u64, range: u64) -> Result<Self, CatMapError> {
+    let last = range.checked_sub(1).ok_or(CatMapError::Empty)?;
+
+    Ok(Self {
+      src,
+      src_end: src.checked_add(last).ok_or(CatMapError::Overflow)?,
+      dest,
+      dest_end: dest.checked_add(last).ok_or(CatMapError::Overflow)?,
+    })
+  }
+
+  fn get(&self, src: u64) -> Option<u64> {
+    (self.src..=self.src_end)
+      .contains(&src)
+      .then(|| self.dest + (src - self.src))
+  }
+
+  /// Resolve a range by splitting it according to the rule.
+  ///
+  /// Range (a,b) and rule (x,y):
+  ///
+  /// Case not generating any resolved range:
+  ///
+  /// Case 1:
+  /// -------a···b--x···y---
+  ///
+  /// Case 2:
+  /// -------x···y--a···b---
+  ///
+  /// Cases generating a resolved range:
+  ///
+  /// Case 3:
+  /// -------a···x··b···y---
+  ///
+  /// Case 4:
+  /// -------a···x··y···b---
+  ///
+  /// Case 5:
+  /// -------x···a··b···y---
+  ///
+  /// Case 6:
+  /// -------x···a··y···b---
+  ///
+  /// Bounds are only stepped past when there is a value beyond them, so that nothing overflows.
+  fn resolve_range(&self, range: Range) -> (Option<Range>, [Option<Range>; 2]) {
+    let (src_end, dest_end) = (self.src_end, self.dest_end);
+
+    if range.1 < self.src || range.0 > src_end {
+      // case 1 & 2
+      (None, [Some(range), None])
+    } else if range.0 < self.src {
+      if range.1 <= src_end {
+        // case 3
+        (
+          Some((self.dest, self.dest + range.1 - self.src)),
+          [Some((range.0, self.src - 1)), None],
+        )
+      } else {
+        // case 4
+        (
+          Some((self.dest, dest_end)),
+          [Some((range.0, self.src - 1)), Some((src_end + 1, range.1))],
+        )
+      }
+    } else if range.1 <= src_end {
+      // case 5
+      (
+        Some((
+          self.dest + range.0 - self.src,
+          self.dest + range.1 - self.src,
+        )),
+        [None, None],
+      )
+    } else {
+      // case 6
+      (
+        Some((self.dest + range.0 - self.src, dest_end)),
+        [Some((src_end + 1, range.1)), None],
+      )
+    }
+  }
+}
+
+/// Rules mapping the values of a category to another one.
+#[derive(Debug)]
+pub struct Maps {
+  /// Source category.
+  src: String,
+  /// Destination category.
+  dest: String,
+  cat_maps: Vec<CatMap>,
+}
+
+impl Maps {
+  pub fn src(&self) -> &str {
+    &self.src
+  }
+
+  pub fn dest(&self) -> &str {
+    &self.dest
+  }
+
+  pub fn get(&self, src: u64) -> u64 {
+    self
+      .cat_maps
+      .iter()
+      .find_map(|cm| cm.get(src))
+      .unwrap_or(src)
+  }
+
+  /// Map all the values of `ranges`, each range being split across all the rules it overlaps.
+  pub fn resolve_ranges(&self, ranges: &IntervalSet) -> IntervalSet {
+    let mut resolved = Vec::new();
+    let mut unresolved = ranges.ranges.clone();
+
+    for map in &self.cat_maps {
+      let mut left = Vec::new();
+
+      for range in unresolved {
+        let (mapped, rest) = map.resolve_range(range);
+        resolved.extend(mapped);
+        left.extend(rest.into_iter().flatten());
+      }
+
+      unresolved = left;
+    }
+
+    IntervalSet::new(resolved.into_iter().chain(unresolved))
+  }
+}
+
+/// Function over all `u64` values, made of consecutive pieces each shifting its values by a
+/// constant.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PiecewiseMap {
+  /// Start of every piece along with its shift, sorted by start, the first piece starting at 0.
+  /// Every piece ends right before the next one, the last one ending at `u64::MAX`.
+  pieces: Vec<(u64, i128)>,
+}
+
+impl PiecewiseMap {
+  pub fn identity() -> Self {
+    Self {
+      pieces: vec![(0, 0)],
+    }
+  }
+
+  /// Pieces of `maps`, the first matching rule winning where rules overlap.
+  pub fn from_maps(maps: &Maps) -> Self {
+    let mut starts: Vec<_> = [0]
+      .into_iter()
+      .chain(
+        maps
+          .cat_maps
+          .iter()
+          .flat_map(|cm| [Some(cm.src), cm.src_end.checked_add(1)])
+          .flatten(),
+      )
+      .collect();
+    starts.sort_unstable();
+    starts.dedup();
+
+    // which rules apply is constant between two consecutive starts
+    Self::merged(starts.into_iter().map(|start| {
+      let shift = maps
+        .cat_maps
+        .iter()
+        .find(|cm| cm.get(start).is_some())
+        .map_or(0, |cm| cm.dest as i128 - cm.src as i128);
+      (start, shift)
+    }))
+  }
+
+  /// Pieces out of possibly redundant ones, merging consecutive pieces of the same shift.
+  fn merged(pieces: impl IntoIterator<Item = (u64, i128)>) -> Self {
+    let mut merged: Vec<(u64, i128)> = Vec::new();
+
+    for (start, shift) in pieces {
+      if merged.last().is_none_or(|&(_, last)| last != shift) {
+        merged.push((start, shift));
+      }
+    }
+
+    Self { pieces: merged }
+  }
+
+  /// Pieces along with their inclusive ends.
+  fn ranges(&self) -> impl Iterator<Item = (Range, i128)> + '_ {
+    let ends = self.pieces.iter().skip(1).map(|&(start, _)| start - 1);
+
+    self
+      .pieces
+      .iter()
+      .zip(ends.chain([u64::MAX]))
+      .map(|(&(start, shift), end)| ((start, end), shift))
+  }
+
+  /// Index of the piece containing `value`.
+  fn piece(&self, value: u64) -> usize {
+    self.pieces.partition_point(|&(start, _)| start <= value) - 1
+  }
+
+  pub fn get(&self, value: u64) -> u64 {
+    shift(value, self.pieces[self.piece(value)].1)
+  }
+
+  /// Map `self`, then `next`.
+  pub fn then(&self, next: &PiecewiseMap) -> Self {
+    let mut pieces = Vec::new();
+    let next_pieces: Vec<_> = next.ranges().collect();
+
+    for ((start, end), shift) in self.ranges() {
+      let image = (self::shift(start, shift), self::shift(end, shift));
+
+      for &((next_start, _), next_shift) in &next_pieces[next.piece(image.0)..] {
+        if next_start > image.1 {
+          break;
+        }
+
+        let from = (next_start as i128 - shift).max(start as i128) as u64;
+        pieces.push((from, shift + next_shift));
+      }
+    }
+
+    Self::merged(pieces)
+  }
+
+  /// Map all the values of `ranges`, in a single sweep over both.
+  pub fn map_ranges(&self, ranges: &IntervalSet) -> IntervalSet {
+    let mut mapped = Vec::new();
+    let pieces: Vec<_> = self.ranges().collect();
+    let mut i = 0;
+
+    for &(a, b) in ranges.ranges() {
+      // ranges are sorted, so pieces ending before them will not be needed again
+      while pieces[i].0 .1 < a {
+        i += 1;
+      }
+
+      for &((start, end), shift) in &pieces[i..] {
+        if start > b {
+          break;
+        }
+
+        mapped.push((
+          self::shift(start.max(a), shift),
+          self::shift(end.min(b), shift),
+        ));
+      }
+    }
+
+    IntervalSet::new(mapped)
+  }
+
+  /// All the values mapped to `value`, of which there may be none or many.
+  pub fn preimage(&self, value: u64) -> IntervalSet {
+    self.preimage_ranges(&IntervalSet::new([(value, value)]))
+  }
+
+  /// All the values mapped into `ranges`.
+  pub fn preimage_ranges(&self, ranges: &IntervalSet) -> IntervalSet {
+    let mut preimage = Vec::new();
+
+    for ((start, end), shift) in self.ranges() {
+      let image = IntervalSet::new([(self::shift(start, shift), self::shift(end, shift))]);
+
+      preimage.extend(
+        image
+          .intersection(ranges)
+          .ranges()
+          .iter()
+          .map(|&(a, b)| (self::shift(a, -shift), self::shift(b, -shift))),
+      );
+    }
+
+    IntervalSet::new(preimage)
+  }
+}
+
+impl fmt::Display for PiecewiseMap {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for ((start, end), shift) in self.ranges() {
+      writeln!(
+        f,
+        "{start}..={end} -> {}..={}",
+        self::shift(start, shift),
+        self::shift(end, shift)
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+/// `value` shifted by `shift`, which must not get it out of the `u64` values.
+fn shift(value: u64, shift: i128) -> u64 {
+  (value as i128 + shift) as u64
+}
+
+/// Seeds and maps between categories, which need not lead from seeds to locations.
+#[derive(Debug)]
+pub struct Almanac {
+  seeds: Vec<u64>,
+  maps: Vec<Maps>,
+}
+
+/// Error raised when mapping values between two categories.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CategoryError {
+  Unknown(String),
+  Unreachable {
+    from: String,
+    to: String,
+  },
+  /// Categories of a cycle, the first one being repeated at the end.
+  Cycle(Vec<String>),
+}
+
+impl fmt::Display for CategoryError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      CategoryError::Unknown(category) => write!(f, "unknown category {category:?}"),
+      CategoryError::Unreachable { from, to } => write!(f, "no maps lead from {from} to {to}"),
+      CategoryError::Cycle(categories) => {
+        write!(f, "maps loop through {}", categories.join(" -> "))
+      }
+    }
+  }
+}
+
+impl Error for CategoryError {}
+
+impl Almanac {
+  pub fn parse(input: &str) -> Result<Self, ParseError> {
+    let mut lines = Lines::new(Day05::DAY, input);
+    let line = lines.expect("a list of seeds")?;
+    let seed_tokens = line
+      .strip_prefix(line.text(), "seeds:")?
+      .split_whitespace()
+      .map(|n| line.parse(n, "a seed").map(|seed: u64| (n, seed)))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    if seed_tokens.is_empty() {
+      return Err(line.error_after(line.text(), "a seed"));
+    }
+
+    // part 2 reads the seeds as ranges, of which there must be at least one
+    if seed_tokens.len() % 2 == 1 {
+      return Err(line.error_after(line.text(), "a seed range length"));
+    }
+
+    if seed_tokens.chunks_exact(2).all(|pair| pair[1].1 == 0) {
+      return Err(line.error(seed_tokens[1].0, "a seed range of positive length"));
+    }
+
+    // read as ranges, the seeds must not go past the largest value either
+    for pair in seed_tokens.chunks_exact(2) {
+      let [(_, start), (len_token, len)] = pair else {
+        unreachable!()
+      };
+
+      if *len > 0 && start.checked_add(len - 1).is_none() {
+        return Err(line.error(
+          len_token,
+          &format!("a valid seed range length ({})", CatMapError::Overflow),
+        ));
+      }
+    }
+
+    let seeds = seed_tokens.into_iter().map(|(_, seed)| seed).collect();
+
+    let mut maps = Vec::new();
+    // line declaring every map
+    let mut declared = HashMap::new();
+
+    while let Some(header) = lines.next() {
+      if header.text().trim().is_empty() {
+        continue;
+      }
+
+      let Some(name) = header.text().strip_suffix(" map:") else {
+        return Err(header.error_after(header.text(), "a map header"));
+      };
+      let (src, dest) = name
+        .split_once("-to-")
+        .ok_or_else(|| header.error(name, "a map name (source-to-destination)"))?;
+
+      header.unique(&mut declared, (src, dest), name, "a map")?;
+
+      let mut cat_maps = Vec::new();
+
+      for line in lines.by_ref() {
+        if line.text().trim().is_empty() {
+          break;
+        }
+
+        let mut numbers = line.text().split_whitespace();
+        let mut number = |expected| {
+          let n = numbers
+            .next()
+            .ok_or_else(|| line.error_after(line.text(), expected))?;
+          line.parse(n, expected).map(|value| (n, value))
+        };
+        let (_, dest) = number("a destination start")?;
+        let (_, src) = number("a source start")?;
+        let (range_token, range) = number("a range length")?;
+
+        if let Some(extra) = numbers.next() {
+          return Err(line.error(extra, "end of line"));
+        }
+
+        let cat_map = CatMap::new(src, dest, range)
+          .map_err(|err| line.error(range_token, &format!("a valid range length ({err})")))?;
+        cat_maps.push(cat_map);
+      }
+
+      maps.push(Maps {
+        src: src.to_owned(),
+        dest: dest.to_owned(),
+        cat_maps,
+      });
+    }
+
+    Ok(Almanac { seeds, maps })
+  }
+
+  pub fn maps(&self) -> &[Maps] {
+    &self.maps
+  }
+
+  /// Map a value of category `from` to category `to`.
+  pub fn resolve(&self, from: &str, to: &str, value: u64) -> Result<u64, CategoryError> {
+    Ok(
+      self
+        .path(from, to)?
+        .into_iter()
+        .fold(value, |value, maps| maps.get(value)),
+    )
+  }
+
+  /// Map from category `from` to category `to`, composing all the maps in between.
+  pub fn map_between(&self, from: &str, to: &str) -> Result<PiecewiseMap, CategoryError> {
+    Ok(
+      self
+        .path(from, to)?
+        .into_iter()
+        .fold(PiecewiseMap::identity(), |composed, maps| {
+          composed.then(&PiecewiseMap::from_maps(maps))
+        }),
+    )
+  }
+
+  /// Maps leading from category `from` to category `to`.
+  ///
+  /// All the categories reachable from `from` are explored depth first, in the order of the
+  /// almanac, the first path found winning. Cycles among them are errors, as they make for endless
+  /// paths, whether they come before or after `to` in the almanac.
+  fn path<'a>(&'a self, from: &'a str, to: &str) -> Result<Vec<&'a Maps>, CategoryError> {
+    for category in [from, to] {
+      if !self
+        .maps
+        .iter()
+        .any(|maps| maps.src == category || maps.dest == category)
+      {
+        return Err(CategoryError::Unknown(category.to_owned()));
+      }
+    }
+
+    let mut categories = vec![from];
+    let mut path = Vec::new();
+    let mut found = None;
+    let mut explored = HashSet::new();
+
+    self.explore(to, &mut categories, &mut path, &mut found, &mut explored)?;
+
+    found.ok_or_else(|| CategoryError::Unreachable {
+      from: from.to_owned(),
+      to: to.to_owned(),
+    })
+  }
+
+  /// Explore all the categories reachable from the last of `categories`, which `path` leads to,
+  /// keeping the first path leading to `to` in `found`.
+  ///
+  /// `explored` are the categories whose reachable categories have all been explored already.
+  fn explore<'a>(
+    &'a self,
+    to: &str,
+    categories: &mut Vec<&'a str>,
+    path: &mut Vec<&'a Maps>,
+    found: &mut Option<Vec<&'a Maps>>,
+    explored: &mut HashSet<&'a str>,
+  ) -> Result<(), CategoryError> {
+    let category = *categories.last().unwrap();
+
+    if category == to && found.is_none() {
+      *found = Some(path.clone());
+    }
+
+    for maps in self.maps.iter().filter(|maps| maps.src == category) {
+      if let Some(i) = categories.iter().position(|c| *c == maps.dest) {
+        let mut cycle: Vec<_> = categories[i..].iter().map(|c| c.to_string()).collect();
+        cycle.push(maps.dest.clone());
+        return Err(CategoryError::Cycle(cycle));
+      }
+
+      if explored.contains(maps.dest.as_str()) {
+        continue;
+      }
+
+      categories.push(&maps.dest);
+      path.push(maps);
+      self.explore(to, categories, path, found, explored)?;
+      categories.pop();
+      path.pop();
+    }
+
+    explored.insert(category);
+    Ok(())
+  }
+
+  fn seeds_as_ranges(&self) -> IntervalSet {
+    IntervalSet::new(
+      self
+        .seeds
+        .iter()
+        .step_by(2)
+        .zip(self.seeds.iter().skip(1).step_by(2))
+        .filter(|(_, len)| **len > 0)
+        .map(|(a, len)| (*a, *a + (*len - 1))),
+    )
+  }
+}
+
+/// Almanac leading from seeds to locations, as both parts read it.
+#[derive(Debug)]
+pub struct SeedAlmanac {
+  almanac: Almanac,
+  /// Map from seeds to locations, composing all the maps in between.
+  seed_to_location: PiecewiseMap,
+}
+
+impl SeedAlmanac {
+  fn parse(input: &str) -> Result<Self, ParseError> {
+    let almanac = Almanac::parse(input)?;
+    let seed_to_location = almanac.map_between("seed", "location").map_err(|err| {
+      let mut lines = Lines::new(Day05::DAY, input);
+      while lines.next().is_some() {}
+      lines.end_error(&format!("maps from seed to location ({err})"))
+    })?;
+
+    Ok(Self {
+      almanac,
+      seed_to_location,
+    })
+  }
+
+  pub fn almanac(&self) -> &Almanac {
+    &self.almanac
+  }
+
+  pub fn seed_to_location(&self) -> &PiecewiseMap {
+    &self.seed_to_location
+  }
+
+  /// All the seeds, listed or not, ending up at `location`.
+  pub fn seeds_for_location(&self, location: u64) -> IntervalSet {
+    self.seed_to_location.preimage(location)
+  }
+
+  /// All the seeds, listed or not, ending up in `locations`.
+  pub fn seeds_for_locations(&self, locations: &IntervalSet) -> IntervalSet {
+    self.seed_to_location.preimage_ranges(locations)
+  }
+}
+
+fn part1(almanac: &SeedAlmanac) -> u64 {
+  almanac
+    .almanac
+    .seeds
+    .iter()
+    .map(|&seed| almanac.seed_to_location.get(seed))
+    .min()
+    .unwrap()
+}
+
+fn part2(almanac: &SeedAlmanac) -> u64 {
+  almanac
+    .seed_to_location
+    .map_ranges(&almanac.almanac.seeds_as_ranges())
+    .min()
+    .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::BTreeSet;
+
+  use aoc_core::{
+    check::{self, rng},
+    Solution,
+  };
+
+  use crate::{
+    Almanac, CatMap, CatMapError, CategoryError, Day05, IntervalSet, Maps, PiecewiseMap,
+  };
+
+  #[test]
+  fn examples() {
+    check::examples::<Day05>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day05>();
+  }
+
+  #[test]
+  fn resolve_ranges() {
+    let map = CatMap::new(10, 100, 20).unwrap();
+
+    assert_eq!(
+      map.resolve_range((10, 29)),
+      (Some((100, 119)), [None, None])
+    );
+    assert_eq!(
+      map.resolve_range((10, 30)),
+      (Some((100, 119)), [Some((30, 30)), None])
+    );
+    assert_eq!(
+      map.resolve_range((10, 35)),
+      (Some((100, 119)), [Some((30, 35)), None])
+    );
+    assert_eq!(map.resolve_range((0, 9)), (None, [Some((0, 9)), None]));
+    assert_eq!(
+      map.resolve_range((0, 10)),
+      (Some((100, 100)), [Some((0, 9)), None])
+    );
+    assert_eq!(
+      map.resolve_range((0, 50)),
+      (Some((100, 119)), [Some((0, 9)), Some((30, 50))])
+    );
+    assert_eq!(map.get(29), Some(119));
+    assert_eq!(map.get(30), None);
+  }
+
+  /// Values of the property tests, small enough to be checked one by one.
+  const DOMAIN: u64 = 120;
+
+  fn random_set(next: &mut impl FnMut() -> u64) -> IntervalSet {
+    let len = next() % 5;
+    IntervalSet::new((0..len).map(|_| {
+      let a = next() % DOMAIN;
+      (a, a + next() % 20)
+    }))
+  }
+
+  fn values(set: &IntervalSet) -> BTreeSet<u64> {
+    set.ranges().iter().flat_map(|&(a, b)| a..=b).collect()
+  }
+
+  #[test]
+  fn interval_sets() {
+    let set = IntervalSet::new([(5, 9), (0, 2), (3, 3), (8, 12), (20, 19)]);
+    assert_eq!(set.ranges(), [(0, 3), (5, 12)]);
+    assert!(set.contains(3) && !set.contains(4) && set.contains(12));
+    assert_eq!(set.min(), Some(0));
+    assert_eq!(
+      IntervalSet::new([(0, u64::MAX - 1), (u64::MAX, u64::MAX)]).ranges(),
+      [(0, u64::MAX)]
+    );
+
+    let mut next = rng(5);
+
+    for _ in 0..500 {
+      let (a, b) = (random_set(&mut next), random_set(&mut next));
+      let union = a.union(&b);
+      let difference = a.difference(&b);
+
+      assert_eq!(union, IntervalSet::new(union.ranges().iter().copied()));
+      assert_eq!(values(&union), &values(&a) | &values(&b), "{a:?} {b:?}");
+      assert_eq!(
+        difference,
+        IntervalSet::new(difference.ranges().iter().copied())
+      );
+      assert_eq!(
+        values(&difference),
+        &values(&a) - &values(&b),
+        "{a:?} {b:?}"
+      );
+    }
+  }
+
+  fn random_maps(next: &mut impl FnMut() -> u64) -> Maps {
+    let len = next() % 5;
+    Maps {
+      src: "a".to_owned(),
+      dest: "b".to_owned(),
+      cat_maps: (0..len)
+        .map(|_| CatMap::new(next() % DOMAIN, next() % DOMAIN, 1 + next() % 30).unwrap())
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn mapping_steps() {
+    let mut next = rng(6);
+
+    for _ in 0..500 {
+      let maps = random_maps(&mut next);
+      let set = random_set(&mut next);
+      let expected: BTreeSet<_> = values(&set).into_iter().map(|v| maps.get(v)).collect();
+
+      assert_eq!(
+        values(&maps.resolve_ranges(&set)),
+        expected,
+        "{maps:?} {set:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn composition() {
+    let almanac = Day05::parse(include_str!("example.txt")).unwrap();
+    let composed = almanac.seed_to_location();
+
+    assert_eq!(
+      [79, 14, 55, 13].map(|seed| composed.get(seed)),
+      [82, 43, 86, 35]
+    );
+    assert!(composed
+      .to_string()
+      .starts_with("0..=13 -> 22..=35\n14..=14 -> 43..=43\n"));
+    assert_eq!(
+      composed.to_string().lines().last(),
+      Some("100..=18446744073709551615 -> 100..=18446744073709551615")
+    );
+
+    let mut next = rng(7);
+
+    for _ in 0..200 {
+      let stages: Vec<_> = (0..1 + next() % 4)
+        .map(|_| random_maps(&mut next))
+        .collect();
+      let composed = stages
+        .iter()
+        .fold(PiecewiseMap::identity(), |composed, maps| {
+          composed.then(&PiecewiseMap::from_maps(maps))
+        });
+
+      for value in 0..DOMAIN * 2 {
+        let expected = stages.iter().fold(value, |value, maps| maps.get(value));
+        assert_eq!(composed.get(value), expected, "{stages:?} {value}");
+      }
+
+      let set = random_set(&mut next);
+      let expected = stages
+        .iter()
+        .fold(set.clone(), |set, maps| maps.resolve_ranges(&set));
+      assert_eq!(composed.map_ranges(&set), expected, "{stages:?} {set:?}");
+    }
+  }
+
+  #[test]
+  fn inverse() {
+    let almanac = Day05::parse(include_str!("example.txt")).unwrap();
+
+    assert_eq!(almanac.seeds_for_location(82).ranges(), [(79, 79)]);
+    assert_eq!(almanac.seeds_for_location(19).ranges(), [(99, 99)]);
+
+    // 10..=19 folded onto 0..=9: many-to-one below 10, a gap above
+    let folded = PiecewiseMap::from_maps(&Maps {
+      src: "a".to_owned(),
+      dest: "b".to_owned(),
+      cat_maps: vec![CatMap::new(10, 0, 10).unwrap()],
+    });
+    assert_eq!(folded.preimage(5).ranges(), [(5, 5), (15, 15)]);
+    assert!(folded.preimage(15).is_empty());
+    assert_eq!(
+      folded
+        .preimage_ranges(&IntervalSet::new([(8, 25)]))
+        .ranges(),
+      [(8, 9), (18, 25)]
+    );
+
+    // the answer to part 2 is the first location reached by seed ranges
+    let seeds = almanac.almanac().seeds_as_ranges();
+    assert!(almanac
+      .seeds_for_locations(&IntervalSet::new([(0, 45)]))
+      .intersection(&seeds)
+      .is_empty());
+    assert_eq!(
+      almanac.seeds_for_location(46).intersection(&seeds).ranges(),
+      [(82, 82)]
+    );
+
+    let mut next = rng(8);
+
+    for _ in 0..200 {
+      let stages: Vec<_> = (0..1 + next() % 4)
+        .map(|_| random_maps(&mut next))
+        .collect();
+      let composed = stages
+        .iter()
+        .fold(PiecewiseMap::identity(), |composed, maps| {
+          composed.then(&PiecewiseMap::from_maps(maps))
+        });
+      // values past the rules map to themselves, so the whole preimage is within the domain
+      let composed = &composed;
+      let brute_force = |value| (0..DOMAIN * 2).filter(move |&x| composed.get(x) == value);
+
+      for value in 0..DOMAIN * 2 {
+        assert_eq!(
+          values(&composed.preimage(value)),
+          brute_force(value).collect(),
+          "{stages:?} {value}"
+        );
+      }
+
+      let set = random_set(&mut next);
+      let expected = values(&set).into_iter().flat_map(brute_force).collect();
+      assert_eq!(
+        values(&composed.preimage_ranges(&set)),
+        expected,
+        "{stages:?} {set:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn categories() {
+    let almanac = Almanac::parse(include_str!("example.txt")).unwrap();
+
+    assert_eq!(almanac.maps()[0].src(), "seed");
+    assert_eq!(almanac.maps()[0].dest(), "soil");
+    // seed 79 has soil 81 and humidity 78
+    assert_eq!(almanac.resolve("soil", "humidity", 81), Ok(78));
+    assert_eq!(almanac.resolve("soil", "soil", 81), Ok(81));
+    assert_eq!(
+      almanac.resolve("humidity", "soil", 78),
+      Err(CategoryError::Unreachable {
+        from: "humidity".to_owned(),
+        to: "soil".to_owned()
+      })
+    );
+    assert_eq!(
+      almanac.resolve("soil", "mud", 81),
+      Err(CategoryError::Unknown("mud".to_owned()))
+    );
+
+    // reordered blocks with a side branch
+    let almanac = Day05::parse(
+      "seeds: 1 2\n\nsoil-to-location map:\n0 1 1\n\nseed-to-dust map:\n9 1 1\n\nseed-to-soil map:\n5 2 1",
+    )
+    .unwrap();
+    assert_eq!(almanac.almanac().resolve("seed", "dust", 1), Ok(9));
+    assert_eq!(almanac.seed_to_location().get(1), 0);
+    assert_eq!(almanac.seed_to_location().get(2), 5);
+
+    let almanac = Almanac::parse(
+      "seeds: 1 1\n\nseed-to-location map:\n\nwater-to-mud map:\n\nmud-to-water map:\n",
+    )
+    .unwrap();
+    let err = almanac.resolve("water", "location", 1).unwrap_err();
+    assert_eq!(
+      err,
+      CategoryError::Cycle(vec!["water".into(), "mud".into(), "water".into()])
+    );
+    assert_eq!(err.to_string(), "maps loop through water -> mud -> water");
+
+    // a cycle reachable from the source is an error, whether it comes before or after the target
+    for blocks in [
+      ["seed-to-soil", "soil-to-location", "soil-to-x", "x-to-soil"],
+      ["seed-to-soil", "soil-to-x", "x-to-soil", "soil-to-location"],
+    ] {
+      let input = blocks.iter().fold("seeds: 1 1".to_owned(), |input, block| {
+        input + "\n\n" + block + " map:\n1 1 1"
+      });
+      let almanac = Almanac::parse(&input).unwrap();
+
+      assert_eq!(
+        almanac.resolve("seed", "location", 1),
+        Err(CategoryError::Cycle(vec![
+          "soil".into(),
+          "x".into(),
+          "soil".into()
+        ])),
+        "{blocks:?}"
+      );
+      assert!(matches!(
+        almanac.resolve("x", "location", 1),
+        Err(CategoryError::Cycle(_))
+      ));
+    }
+
+    // variants need not lead to locations, but the puzzle does
+    let input = "seeds: 1 1\n\nseed-to-soil map:\n1 2 3";
+    let almanac = Almanac::parse(input).unwrap();
+    assert_eq!(almanac.resolve("seed", "soil", 3), Ok(2));
+    assert_eq!(
+      almanac.map_between("seed", "location").unwrap_err(),
+      CategoryError::Unknown("location".to_owned())
+    );
+    let err = Day05::parse(input).unwrap_err();
+    assert_eq!((err.line(), err.column()), (5, 1));
+    assert_eq!(
+      err.expected(),
+      "maps from seed to location (unknown category \"location\")"
+    );
+
+    let err = Day05::parse("seeds: 1 1\n\nseed-soil map:\n1 2 3").unwrap_err();
+    assert_eq!((err.line(), err.column()), (3, 1));
+
+    // neither part has anything to map without seeds
+    let err = Day05::parse("seeds: \n\nseed-to-location map:").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 8));
+    assert_eq!(err.expected(), "a seed");
+
+    // part 2 has nothing to map without a seed range
+    let err = Day05::parse("seeds: 79\n\nseed-to-location map:").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 10));
+    assert_eq!(err.expected(), "a seed range length");
+
+    let err = Day05::parse("seeds: 7 0 12 0\n\nseed-to-location map:").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 10));
+    assert_eq!(err.expected(), "a seed range of positive length");
+  }
+
+  #[test]
+  fn extreme_values() {
+    const MAX: u64 = u64::MAX;
+
+    assert_eq!(CatMap::new(5, 5, 0).unwrap_err(), CatMapError::Empty);
+    assert_eq!(CatMap::new(MAX, 0, 2).unwrap_err(), CatMapError::Overflow);
+    assert_eq!(
+      CatMap::new(0, MAX - 1, 3).unwrap_err(),
+      CatMapError::Overflow
+    );
+
+    // the last two values swapped with the first two
+    let high = CatMap::new(MAX - 1, 0, 2).unwrap();
+    let low = CatMap::new(0, MAX - 1, 2).unwrap();
+    assert_eq!(high.get(MAX), Some(1));
+    assert_eq!(high.get(MAX - 2), None);
+    assert_eq!(low.get(1), Some(MAX));
+    assert_eq!(
+      high.resolve_range((0, MAX)),
+      (Some((0, 1)), [Some((0, MAX - 2)), None])
+    );
+    assert_eq!(
+      low.resolve_range((0, MAX)),
+      (Some((MAX - 1, MAX)), [Some((2, MAX)), None])
+    );
+
+    let maps = Maps {
+      src: "a".to_owned(),
+      dest: "b".to_owned(),
+      cat_maps: vec![high, low],
+    };
+    let all = IntervalSet::new([(0, MAX)]);
+    assert_eq!(maps.resolve_ranges(&all), all);
+
+    let composed = PiecewiseMap::from_maps(&maps);
+    assert_eq!(composed.get(MAX), 1);
+    assert_eq!(composed.get(0), MAX - 1);
+    assert_eq!(composed.map_ranges(&all), all);
+    assert_eq!(composed.preimage(MAX).ranges(), [(1, 1)]);
+    assert_eq!(composed.then(&composed), PiecewiseMap::identity());
+
+    let err = Day05::parse("seeds: 1 1\n\nseed-to-location map:\n1 2 0").unwrap_err();
+    assert_eq!((err.line(), err.column()), (4, 5));
+    assert_eq!(err.expected(), "a valid range length (empty range)");
+
+    // seed ranges reaching the largest value, and one going past it
+    let almanac = Day05::parse(&format!(
+      "seeds: {} 2 {MAX} 1 7 0\n\nseed-to-location map:",
+      MAX - 1
+    ))
+    .unwrap();
+    assert_eq!(
+      almanac.almanac().seeds_as_ranges().ranges(),
+      [(MAX - 1, MAX)]
+    );
+    assert_eq!(Day05::part2(&almanac), MAX - 1);
+
+    let err = Day05::parse(&format!("seeds: 1 1 {MAX} 2\n\nseed-to-location map:")).unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 33));
+    assert_eq!(
+      err.expected(),
+      "a valid seed range length (range past the largest value)"
+    );
+  }
+}
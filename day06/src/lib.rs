@@ -0,0 +1,263 @@
+use aoc_core::{
+  check::Example,
+  parse::{Line, Lines, ParseError},
+  Solution,
+};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  /// Races as read for part 1 and for part 2 (kerning ignored).
+  type Input = (Vec<Race>, Vec<Race>);
+  type Output1 = u128;
+  type Output2 = u128;
+
+  const DAY: u8 = 6;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("288")
+    .part2("71503")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    Ok((parse(input)?, parse2(input)?))
+  }
+
+  fn part1(input: &Self::Input) -> Self::Output1 {
+    solve(&input.0)
+  }
+
+  fn part2(input: &Self::Input) -> Self::Output2 {
+    solve(&input.1)
+  }
+}
+
+#[derive(Debug)]
+pub struct Race {
+  time: u128,
+  dist: u128,
+}
+
+impl Race {
+  /// Number of ways to beat the record.
+  ///
+  /// Holding the button for `p` milliseconds wins if `p (T - p) > D`, i.e. `p² - T p + D < 0`, so
+  /// `p` must lie strictly between the roots `(T ± √Δ) / 2`, with `Δ = T² - 4D`. Put another way,
+  /// `q = 2p - T` must satisfy `|q| < √Δ`, and has the same parity as `T`. Counting such values of
+  /// `q` only requires the integer square root of `Δ`, which is computed on 256 bits so that races
+  /// up to `u128::MAX` are supported.
+  fn win_presses(&self) -> u128 {
+    let time2 = mul_wide(self.time, self.time);
+    let dist4 = (self.dist >> 126, self.dist << 2);
+
+    if time2 <= dist4 {
+      return 0;
+    }
+
+    let delta = sub_wide(time2, dist4);
+    let root = isqrt_wide(delta);
+
+    // largest |q| such that q² < Δ
+    let max = if mul_wide(root, root) == delta {
+      match root.checked_sub(1) {
+        Some(max) => max,
+        None => return 0,
+      }
+    } else {
+      root
+    };
+
+    if self.time.is_multiple_of(2) {
+      max / 2 * 2 + 1
+    } else {
+      max.div_ceil(2) * 2
+    }
+  }
+}
+
+/// 256-bit unsigned integer, as its high and low halves; tuples order like the integers they
+/// represent.
+type Wide = (u128, u128);
+
+/// Full product of `a` and `b`.
+fn mul_wide(a: u128, b: u128) -> Wide {
+  let (a1, a0) = (a >> 64, a & u64::MAX as u128);
+  let (b1, b0) = (b >> 64, b & u64::MAX as u128);
+  let (mid, mid_carry) = (a1 * b0).overflowing_add(a0 * b1);
+  let (lo, lo_carry) = (a0 * b0).overflowing_add(mid << 64);
+  let hi = a1 * b1 + (mid >> 64) + ((mid_carry as u128) << 64) + lo_carry as u128;
+  (hi, lo)
+}
+
+/// `a - b`, with `a >= b`.
+fn sub_wide(a: Wide, b: Wide) -> Wide {
+  let (lo, borrow) = a.1.overflowing_sub(b.1);
+  (a.0 - b.0 - borrow as u128, lo)
+}
+
+/// Integer square root of `n`, i.e. the largest `r` such that `r² <= n`.
+fn isqrt_wide(n: Wide) -> u128 {
+  let (mut lo, mut hi) = (0, u128::MAX);
+
+  while lo < hi {
+    let mid = lo + (hi - lo) / 2 + 1;
+
+    if mul_wide(mid, mid) <= n {
+      lo = mid;
+    } else {
+      hi = mid - 1;
+    }
+  }
+
+  lo
+}
+
+fn parse(input: &str) -> Result<Vec<Race>, ParseError> {
+  races(input, |line| {
+    let (_, numbers) = line.split_once(line.text(), ':')?;
+    numbers
+      .split_whitespace()
+      .map(|n| line.parse(n, "a number"))
+      .collect()
+  })
+}
+
+fn parse2(input: &str) -> Result<Vec<Race>, ParseError> {
+  races(input, |line| {
+    let (_, number) = line.split_once(line.text(), ':')?;
+    let number = number.trim();
+    let n = number
+      .replace(' ', "")
+      .parse()
+      .map_err(|_| line.error(number, "a number"))?;
+    Ok(vec![n])
+  })
+}
+
+fn races(
+  input: &str,
+  numbers: impl Fn(Line) -> Result<Vec<u128>, ParseError>,
+) -> Result<Vec<Race>, ParseError> {
+  let mut lines = Lines::new(Day06::DAY, input);
+  let times = numbers(lines.expect("race times")?)?;
+  let line = lines.expect("race distances")?;
+  let dist = numbers(line)?;
+
+  if dist.len() != times.len() {
+    return Err(line.error_after(
+      line.text(),
+      &format!("as many distances as times ({})", times.len()),
+    ));
+  }
+
+  Ok(
+    times
+      .into_iter()
+      .zip(dist)
+      .map(|(time, dist)| Race { time, dist })
+      .collect(),
+  )
+}
+
+fn solve(races: &[Race]) -> u128 {
+  races.iter().map(|race| race.win_presses()).product()
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::check::{self, rng};
+
+  use crate::{Day06, Race};
+
+  /// Reference implementation, scanning all press times.
+  fn win_presses_brute(race: &Race) -> u128 {
+    (1..race.time)
+      .map(|time_pressed| time_pressed * (race.time - time_pressed))
+      .filter(|dist| *dist > race.dist)
+      .count() as _
+  }
+
+  /// Reference implementation, searching for the shortest winning press time.
+  fn win_presses_search(race: &Race) -> u128 {
+    let wins = |p: u128| p.checked_mul(race.time - p).is_none_or(|d| d > race.dist);
+    let half = race.time / 2;
+
+    if !wins(half) {
+      return 0;
+    }
+
+    let (mut lo, mut hi) = (0, half);
+    while lo < hi {
+      let mid = lo + (hi - lo) / 2;
+      if wins(mid) {
+        hi = mid;
+      } else {
+        lo = mid + 1;
+      }
+    }
+
+    race.time - 2 * lo + 1
+  }
+
+  #[test]
+  fn examples() {
+    check::examples::<Day06>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day06>();
+  }
+
+  #[test]
+  fn win_presses_small() {
+    let mut next = rng(0x5eed);
+
+    for _ in 0..10_000 {
+      let time = (next() % 300) as u128;
+      let dist = (next() as u128) % (time * time / 4 + 10);
+      let race = Race { time, dist };
+
+      assert_eq!(race.win_presses(), win_presses_brute(&race), "{race:?}");
+    }
+  }
+
+  #[test]
+  fn win_presses_large() {
+    let mut next = rng(0xdeadbeef);
+
+    for i in 0..10_000 {
+      let time = if i % 2 == 0 {
+        next() as u128
+      } else {
+        (next() as u128) << 64 | next() as u128
+      };
+      let dist = ((next() as u128) << 64 | next() as u128) >> (next() % 128);
+      let race = Race { time, dist };
+
+      assert_eq!(race.win_presses(), win_presses_search(&race), "{race:?}");
+    }
+  }
+
+  #[test]
+  fn win_presses_edges() {
+    let races = [
+      (0, 0),
+      (1, 0),
+      (2, 0),
+      (2, 1),
+      (4, 4),
+      (u128::MAX, 0),
+      (u128::MAX - 1, 0),
+    ];
+
+    for (time, dist) in races {
+      let race = Race { time, dist };
+      assert_eq!(race.win_presses(), win_presses_search(&race), "{race:?}");
+    }
+
+    let race = Race {
+      time: u128::MAX,
+      dist: 0,
+    };
+    assert_eq!(race.win_presses(), u128::MAX - 1);
+  }
+}
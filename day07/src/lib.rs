@@ -0,0 +1,239 @@
+use std::cmp::Reverse;
+
+use aoc_core::{
+  check::Example,
+  parse::{Lines, ParseError},
+  Solution,
+};
+use itertools::Itertools;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  type Input = Vec<Hand>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  const DAY: u8 = 7;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("6440")
+    .part2("5905")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    parse(input)
+  }
+
+  fn part1(input: &Self::Input) -> Self::Output1 {
+    solve(input, false)
+  }
+
+  fn part2(input: &Self::Input) -> Self::Output2 {
+    solve(input, true)
+  }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Card {
+  NJ,
+  N2,
+  N3,
+  N4,
+  N5,
+  N6,
+  N7,
+  N8,
+  N9,
+  T,
+  J,
+  Q,
+  K,
+  A,
+}
+
+impl Card {
+  fn all() -> &'static [Self] {
+    &[
+      Self::NJ,
+      Self::N2,
+      Self::N3,
+      Self::N4,
+      Self::N5,
+      Self::N6,
+      Self::N7,
+      Self::N8,
+      Self::N9,
+      Self::T,
+      Self::J,
+      Self::Q,
+      Self::K,
+      Self::A,
+    ]
+  }
+
+  fn parse(c: char) -> Option<Self> {
+    match c {
+      '2' => Some(Self::N2),
+      '3' => Some(Self::N3),
+      '4' => Some(Self::N4),
+      '5' => Some(Self::N5),
+      '6' => Some(Self::N6),
+      '7' => Some(Self::N7),
+      '8' => Some(Self::N8),
+      '9' => Some(Self::N9),
+      'T' => Some(Self::T),
+      'J' => Some(Self::J),
+      'Q' => Some(Self::Q),
+      'K' => Some(Self::K),
+      'A' => Some(Self::A),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Type {
+  HighCard,
+  OnePair,
+  TwoPair,
+  ThreeOfAKind,
+  FullHouse,
+  FourOfAKind,
+  FiveOfAKind,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hand {
+  cards: Vec<Card>,
+  bid: usize,
+}
+
+impl Hand {
+  fn best_j(&self) -> Type {
+    Card::all()
+      .iter()
+      .map(|new_card| {
+        let cards = self
+          .cards
+          .iter()
+          .map(|card| if let Card::J = card { *new_card } else { *card })
+          .collect();
+        Hand {
+          cards,
+          bid: self.bid,
+        }
+        .ty(false)
+      })
+      .max()
+      .unwrap()
+  }
+
+  fn replace_j(&self) -> Self {
+    let cards = self
+      .cards
+      .iter()
+      .map(|card| if let Card::J = card { Card::NJ } else { *card })
+      .collect();
+    Self {
+      cards,
+      bid: self.bid,
+    }
+  }
+
+  fn ty(&self, j: bool) -> Type {
+    if j {
+      return self.best_j();
+    }
+
+    let mut cards = self.cards.clone();
+    cards.sort();
+
+    let groups = cards.into_iter().group_by(|x| *x);
+    let mut grouped: Vec<_> = groups.into_iter().map(|(k, g)| (k, g.count())).collect();
+    grouped.sort_by_key(|(_, len)| Reverse(*len));
+
+    if grouped.len() == 1 {
+      Type::FiveOfAKind
+    } else if grouped[0].1 == 4 {
+      Type::FourOfAKind
+    } else if grouped[0].1 == 3 {
+      if grouped.len() == 2 {
+        Type::FullHouse
+      } else {
+        Type::ThreeOfAKind
+      }
+    } else if grouped[0].1 == 2 {
+      if grouped.len() == 3 {
+        Type::TwoPair
+      } else {
+        Type::OnePair
+      }
+    } else {
+      Type::HighCard
+    }
+  }
+}
+
+fn parse(input: &str) -> Result<Vec<Hand>, ParseError> {
+  Lines::new(Day07::DAY, input)
+    .map(|line| {
+      let mut parts = line.text().split_whitespace();
+      let hand = parts
+        .next()
+        .ok_or_else(|| line.error(line.text(), "a hand"))?;
+      let cards = hand
+        .char_indices()
+        .map(|(i, c)| {
+          Card::parse(c)
+            .ok_or_else(|| line.error(&hand[i..i + c.len_utf8()], "a card (23456789TJQKA)"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+      if cards.len() != 5 {
+        return Err(line.error(hand, "a hand of 5 cards"));
+      }
+
+      let bid = parts
+        .next()
+        .ok_or_else(|| line.error_after(hand, "a bid"))?;
+      let bid = line.parse(bid, "a bid")?;
+
+      Ok(Hand { cards, bid })
+    })
+    .collect()
+}
+
+fn solve(hands: &[Hand], j: bool) -> usize {
+  let mut hands = hands.to_owned();
+  hands.sort_by_key(|hand| {
+    (
+      hand.ty(j),
+      if j {
+        hand.replace_j().cards
+      } else {
+        hand.cards.clone()
+      },
+    )
+  });
+  hands
+    .iter()
+    .enumerate()
+    .map(|(rank, hand)| (rank + 1) * hand.bid)
+    .sum()
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::check;
+
+  use crate::Day07;
+
+  #[test]
+  fn examples() {
+    check::examples::<Day07>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day07>();
+  }
+}
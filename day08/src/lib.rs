@@ -0,0 +1,190 @@
+use std::{collections::HashMap, fmt};
+
+use aoc_core::{
+  check::Example,
+  parse::{Lines, ParseError},
+  Solution,
+};
+use num::Integer;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+  type Input = (Dirs, Nodes);
+  type Output1 = Steps;
+  type Output2 = usize;
+
+  const DAY: u8 = 8;
+  const EXAMPLES: &'static [Example] = &[
+    Example::new(include_str!("example1.txt")).part1("2"),
+    Example::new(include_str!("example2.txt")).part1("6"),
+    Example::new(include_str!("example3.txt")).part2("6"),
+  ];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    parse(input)
+  }
+
+  fn part1((dirs, nodes): &Self::Input) -> Self::Output1 {
+    Steps(
+      nodes
+        .nodes
+        .contains_key(START)
+        .then(|| solve(dirs, nodes, vec![START.to_string()])),
+    )
+  }
+
+  fn part2((dirs, nodes): &Self::Input) -> Self::Output2 {
+    solve(dirs, nodes, nodes.starts())
+  }
+}
+
+/// Node part 1 starts from.
+const START: &str = "AAA";
+
+/// Steps taken by part 1, if the network has a node to start from.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Steps(Option<usize>);
+
+impl fmt::Display for Steps {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.0 {
+      Some(steps) => steps.fmt(f),
+      None => write!(f, "no starting node {START}"),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct Dirs {
+  dirs: Vec<char>,
+}
+
+#[derive(Debug)]
+pub struct Nodes {
+  nodes: HashMap<String, (String, String)>,
+}
+
+impl Nodes {
+  fn starts(&self) -> Vec<String> {
+    self
+      .nodes
+      .keys()
+      .filter(|node| node.ends_with('A'))
+      .cloned()
+      .collect()
+  }
+}
+
+fn parse(input: &str) -> Result<(Dirs, Nodes), ParseError> {
+  let mut lines = Lines::new(Day08::DAY, input);
+  let line = lines.expect("a list of directions")?;
+  let dirs = Dirs {
+    dirs: line
+      .text()
+      .char_indices()
+      .map(|(i, c)| match c {
+        'L' | 'R' => Ok(c),
+        _ => Err(line.error(&line.text()[i..i + c.len_utf8()], "a direction (L or R)")),
+      })
+      .collect::<Result<_, _>>()?,
+  };
+
+  if dirs.dirs.is_empty() {
+    return Err(line.error(line.text(), "a direction (L or R)"));
+  }
+
+  let mut nodes = Nodes {
+    nodes: HashMap::new(),
+  };
+  // nodes referenced by the others, along with the line doing so
+  let mut references = Vec::new();
+
+  for line in lines.filter(|line| !line.text().trim().is_empty()) {
+    let (key, pair) = line.split_once(line.text(), '=')?;
+    let pair = line.strip_prefix(pair.trim(), "(")?;
+    let (left, right) = line.split_once(pair, ',')?;
+    let right = right.trim();
+    let right = right
+      .strip_suffix(')')
+      .ok_or_else(|| line.error_after(right, "')'"))?;
+    let (left, right) = (left.trim(), right.trim());
+
+    nodes
+      .nodes
+      .insert(key.trim().to_owned(), (left.to_owned(), right.to_owned()));
+    references.extend([(line, left), (line, right)]);
+  }
+
+  if let Some((line, node)) = references
+    .into_iter()
+    .find(|(_, node)| !nodes.nodes.contains_key(*node))
+  {
+    return Err(line.error(node, "a node defined in the network"));
+  }
+
+  Ok((dirs, nodes))
+}
+
+fn solve(dirs: &Dirs, nodes: &Nodes, starts: Vec<String>) -> usize {
+  let mut dirs = dirs.dirs.iter().cycle();
+  let mut steps = vec![0; starts.len()];
+  let mut current_nodes = starts;
+
+  loop {
+    let dir = dirs.next().unwrap();
+
+    for (i, current_node) in current_nodes.iter_mut().enumerate() {
+      if current_node.ends_with('Z') {
+        continue;
+      }
+
+      let (left, right) = nodes.nodes.get(current_node.as_str()).unwrap();
+
+      match dir {
+        'L' => *current_node = left.clone(),
+        'R' => *current_node = right.clone(),
+        _ => (),
+      }
+
+      steps[i] += 1;
+    }
+
+    if current_nodes.iter().all(|node| node.ends_with('Z')) {
+      break;
+    }
+  }
+
+  steps.iter().fold(1, |acc, step| step.lcm(&acc))
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::{check, Solution};
+
+  use crate::{Day08, Steps};
+
+  #[test]
+  fn examples() {
+    check::examples::<Day08>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day08>();
+  }
+
+  #[test]
+  fn missing_nodes() {
+    let err =
+      Day08::parse("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, CCC)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+    assert_eq!((err.line(), err.column()), (4, 13));
+    assert_eq!(err.expected(), "a node defined in the network");
+
+    // the example of part 2 has no node to start part 1 from
+    let input = Day08::parse(include_str!("example3.txt")).unwrap();
+    assert_eq!(Day08::part1(&input), Steps(None));
+    assert_eq!(Day08::part1(&input).to_string(), "no starting node AAA");
+    assert_eq!(Day08::part2(&input), 6);
+  }
+}
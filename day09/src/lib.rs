@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use aoc_core::{
+  check::Example,
+  parse::{Lines, ParseError},
+  Solution,
+};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+  type Input = Vec<Vec<i64>>;
+  type Output1 = i64;
+  type Output2 = i64;
+
+  const DAY: u8 = 9;
+  const EXAMPLES: &'static [Example] = &[Example::new(include_str!("example.txt"))
+    .part1("114")
+    .part2("2")];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    parse(input)
+  }
+
+  fn part1(input: &Self::Input) -> Self::Output1 {
+    part1(input)
+  }
+
+  fn part2(input: &Self::Input) -> Self::Output2 {
+    part2(input)
+  }
+}
+
+fn parse(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+  Lines::new(Day09::DAY, input)
+    .map(|line| {
+      let hist: Vec<_> = line
+        .text()
+        .split_whitespace()
+        .map(|n| line.parse(n, "a number"))
+        .collect::<Result<_, _>>()?;
+
+      // a single value has no differences to extrapolate from
+      if hist.len() < 2 {
+        return Err(line.error_after(line.text(), "a history of at least two numbers"));
+      }
+
+      Ok(hist)
+    })
+    .collect()
+}
+
+fn hist_next(hist: &[i64]) -> i64 {
+  let diffs: Vec<_> = hist
+    .iter()
+    .zip(hist.iter().skip(1))
+    .map(|(a, b)| b - a)
+    .collect();
+
+  let set: HashSet<_> = diffs.iter().collect();
+  if set.len() == 1 {
+    // no need to go any deeper; start going back up with this value
+    hist.last().unwrap() + *diffs.last().unwrap()
+  } else {
+    hist.last().unwrap() + hist_next(&diffs)
+  }
+}
+
+fn hist_prev(hist: &[i64]) -> i64 {
+  let diffs: Vec<_> = hist
+    .iter()
+    .zip(hist.iter().skip(1))
+    .map(|(a, b)| b - a)
+    .collect();
+
+  let set: HashSet<_> = diffs.iter().collect();
+  if set.len() == 1 {
+    // no need to go any deeper; start going back up with this value
+    hist.first().unwrap() - *diffs.first().unwrap()
+  } else {
+    hist.first().unwrap() - hist_prev(&diffs)
+  }
+}
+
+fn part1(hist: &[Vec<i64>]) -> i64 {
+  hist.iter().map(|hist| hist_next(hist)).sum()
+}
+
+fn part2(hist: &[Vec<i64>]) -> i64 {
+  hist.iter().map(|hist| hist_prev(hist)).sum()
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::{check, Solution};
+
+  use crate::Day09;
+
+  #[test]
+  fn examples() {
+    check::examples::<Day09>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day09>();
+  }
+
+  #[test]
+  fn short_histories() {
+    assert_eq!(Day09::parse("1 2\n-3 5").unwrap(), [[1, 2], [-3, 5]]);
+
+    for (input, column) in [("0 3 6\n7", 2), ("0 3 6\n\n1 2", 1)] {
+      let err = Day09::parse(input).unwrap_err();
+      assert_eq!((err.line(), err.column()), (2, column));
+      assert_eq!(err.expected(), "a history of at least two numbers");
+    }
+  }
+}
@@ -0,0 +1,255 @@
+use aoc_core::{
+  check::Example,
+  grid::{Dir, Grid, Pos},
+  parse::{Lines, ParseError},
+  Solution,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  type Input = Maze;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  const DAY: u8 = 10;
+  const EXAMPLES: &'static [Example] = &[
+    Example::new(include_str!("example1.txt")).part1("4"),
+    Example::new(include_str!("example2.txt")).part1("8"),
+    Example::new(include_str!("example3.txt")).part2("4"),
+    Example::new(include_str!("example4.txt")).part2("4"),
+    Example::new(include_str!("example5.txt")).part2("8"),
+    Example::new(include_str!("example6.txt")).part2("10"),
+  ];
+
+  fn parse(input: &str) -> Result<Self::Input, ParseError> {
+    Maze::parse(input)
+  }
+
+  fn part1(maze: &Self::Input) -> Self::Output1 {
+    maze.pipe_loop.farthest().1
+  }
+
+  fn part2(maze: &Self::Input) -> Self::Output2 {
+    maze.pipe_loop.enclosed_area()
+  }
+}
+
+const TILES: &str = "|-LJ7F.S";
+
+/// Pipes the starting tile may hide.
+const PIPES: &[u8] = b"|-LJ7F";
+
+#[derive(Debug)]
+pub struct Maze {
+  pipe_loop: Loop,
+}
+
+impl Maze {
+  fn parse(input: &str) -> Result<Self, ParseError> {
+    let mut grid = Grid::parse(Day10::DAY, input, "a tile (|-LJ7F.S)", |c| {
+      TILES.contains(c).then_some(c as u8)
+    })?;
+
+    let Some(start) = grid.find(|&tile| tile == b'S') else {
+      let mut lines = Lines::new(Day10::DAY, input);
+      lines.by_ref().for_each(drop);
+      return Err(lines.end_error("a starting tile S"));
+    };
+    let error_at = |pos: Pos, expected: &str| {
+      let line = Lines::new(Day10::DAY, input).nth(pos.row).unwrap();
+      line.error(&line.text()[pos.col..pos.col + 1], expected)
+    };
+
+    let pipe_loop = start_loop(&mut grid, start).map_err(|expected| error_at(start, expected))?;
+
+    Ok(Self { pipe_loop })
+  }
+
+  pub fn pipe_loop(&self) -> &Loop {
+    &self.pipe_loop
+  }
+}
+
+/// The loop going through the starting tile.
+#[derive(Debug)]
+pub struct Loop {
+  /// Pipe hidden under the starting tile.
+  start_pipe: u8,
+
+  /// Tiles of the loop, in order from the starting tile, each along with the direction leaving it.
+  path: Vec<(Pos, Dir)>,
+}
+
+impl Loop {
+  /// Follow the pipes of `grid` from `start` until coming back to it, if they do.
+  fn traverse(grid: &Grid<u8>, start: Pos) -> Option<Self> {
+    let start_pipe = grid[start];
+    let mut path = Vec::new();
+    let mut pos = start;
+    let mut dir = connections(start_pipe)[0];
+
+    loop {
+      path.push((pos, dir));
+
+      let next = pos.step(dir).filter(|next| grid.contains(*next))?;
+      let from = dir.opposite();
+      let exits = connections(grid[next]);
+
+      if !exits.contains(&from) {
+        return None;
+      }
+
+      if next == start {
+        break;
+      }
+
+      pos = next;
+      dir = exits.iter().copied().find(|&exit| exit != from).unwrap();
+    }
+
+    Some(Self { start_pipe, path })
+  }
+
+  pub fn start(&self) -> Pos {
+    self.path[0].0
+  }
+
+  pub fn start_pipe(&self) -> char {
+    self.start_pipe as char
+  }
+
+  pub fn path(&self) -> &[(Pos, Dir)] {
+    &self.path
+  }
+
+  /// Tile of the loop the farthest from the starting tile, along with its distance.
+  pub fn farthest(&self) -> (Pos, usize) {
+    let distance = self.path.len() / 2;
+    (self.path[distance].0, distance)
+  }
+
+  /// Number of tiles enclosed by the loop.
+  ///
+  /// The shoelace formula gives the area `A` of the polygon going through the centres of the loop
+  /// tiles. Those centres lie on integer coordinates, so Pick's theorem, `A = i + b / 2 - 1`, gives
+  /// the number `i` of interior tiles out of the `b` tiles of the loop.
+  pub fn enclosed_area(&self) -> usize {
+    let double_area = self
+      .path
+      .iter()
+      .zip(self.path.iter().cycle().skip(1))
+      .map(|((a, _), (b, _))| a.col as i64 * b.row as i64 - b.col as i64 * a.row as i64)
+      .sum::<i64>()
+      .unsigned_abs() as usize;
+
+    (double_area + 2 - self.path.len()) / 2
+  }
+}
+
+/// Loop through the starting tile, trying every pipe it may hide.
+///
+/// Neighbours may connect to the starting tile without being part of the loop, so it is the pipe
+/// closing a loop which is kept. Fail with what was expected if no pipe does, or several do.
+fn start_loop(grid: &mut Grid<u8>, start: Pos) -> Result<Loop, &'static str> {
+  let mut found = None;
+
+  for &pipe in PIPES {
+    grid[start] = pipe;
+
+    if let Some(pipe_loop) = Loop::traverse(grid, start) {
+      if found.is_some() {
+        return Err("a starting tile on a single loop");
+      }
+
+      found = Some(pipe_loop);
+    }
+  }
+
+  found.ok_or("a starting tile on a closed loop")
+}
+
+/// Directions a tile connects to.
+fn connections(tile: u8) -> &'static [Dir] {
+  match tile {
+    b'|' => &[Dir::Up, Dir::Down],
+    b'-' => &[Dir::Left, Dir::Right],
+    b'L' => &[Dir::Up, Dir::Right],
+    b'J' => &[Dir::Up, Dir::Left],
+    b'7' => &[Dir::Down, Dir::Left],
+    b'F' => &[Dir::Down, Dir::Right],
+    _ => &[],
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use aoc_core::{
+    check,
+    grid::{Dir, Pos},
+  };
+
+  use crate::{Day10, Maze};
+
+  #[test]
+  fn examples() {
+    check::examples::<Day10>();
+  }
+
+  #[test]
+  fn input() {
+    check::input::<Day10>();
+  }
+
+  #[test]
+  fn start_pipe() {
+    let maze = Maze::parse(include_str!("example1.txt")).unwrap();
+    assert_eq!(maze.pipe_loop().start(), Pos::new(1, 1));
+    assert_eq!(maze.pipe_loop().start_pipe(), 'F');
+
+    let maze = Maze::parse(include_str!("example2.txt")).unwrap();
+    assert_eq!(maze.pipe_loop().start(), Pos::new(2, 0));
+    assert_eq!(maze.pipe_loop().start_pipe(), 'F');
+
+    let err = Maze::parse(".|.\n-S-\n.|.").unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 2));
+    assert_eq!(err.expected(), "a starting tile on a closed loop");
+
+    // three neighbours connect to S, only two of them along the loop
+    let maze = Maze::parse(".|...\n.S-7.\n.|.|.\n.L-J.").unwrap();
+    assert_eq!(maze.pipe_loop().start_pipe(), 'F');
+    assert_eq!(maze.pipe_loop().farthest().1, 4);
+
+    // two loops through S
+    let err = Maze::parse("F-7..\n|.|..\nL-S-7\n..|.|\n..L-J").unwrap_err();
+    assert_eq!((err.line(), err.column()), (3, 3));
+    assert_eq!(err.expected(), "a starting tile on a single loop");
+  }
+
+  #[test]
+  fn ordered_loop() {
+    let maze = Maze::parse(include_str!("example1.txt")).unwrap();
+    let pipe_loop = maze.pipe_loop();
+
+    assert_eq!(pipe_loop.start(), Pos::new(1, 1));
+    assert_eq!(
+      pipe_loop.path(),
+      [
+        (Pos::new(1, 1), Dir::Down),
+        (Pos::new(2, 1), Dir::Down),
+        (Pos::new(3, 1), Dir::Right),
+        (Pos::new(3, 2), Dir::Right),
+        (Pos::new(3, 3), Dir::Up),
+        (Pos::new(2, 3), Dir::Up),
+        (Pos::new(1, 3), Dir::Left),
+        (Pos::new(1, 2), Dir::Left),
+      ]
+    );
+    assert_eq!(pipe_loop.farthest(), (Pos::new(3, 3), 4));
+    assert_eq!(pipe_loop.enclosed_area(), 1);
+
+    // the loop would close through the '.'
+    let err = Maze::parse("S-7\n|..\nL-J").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 1));
+  }
+}